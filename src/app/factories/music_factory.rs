@@ -3,9 +3,18 @@ use std::path::Path;
 
 use sdl2::mixer::{AUDIO_S16LSB, Chunk, DEFAULT_CHANNELS, Music, Sdl2MixerContext};
 
+use crate::core::musics::mixer::SoundDef;
+use crate::core::musics::playlist::Playlist;
+
+pub const NB_CANAUX: i32 = 16;
+// les premiers canaux sont reserves aux couches de musique adaptative
+pub const NB_CANAUX_COUCHES: i32 = 2;
+
 pub struct MusicFactory<'m> {
     pub musics: HashMap<&'m str, Music<'m>>,
-    pub sounds: HashMap<&'m str, Chunk>,
+    pub sounds: HashMap<String, Chunk>,
+    pub sound_defs: HashMap<String, SoundDef>,
+    pub playlists: HashMap<String, Playlist>,
     _context_mixer: Sdl2MixerContext,
 }
 
@@ -16,7 +25,8 @@ impl<'m> MusicFactory<'m> {
         let channels = DEFAULT_CHANNELS; // Stereo
         let chunk_size = 1_024;
         sdl2::mixer::open_audio(frequency, format, channels, chunk_size)?;
-        sdl2::mixer::allocate_channels(4);
+        sdl2::mixer::allocate_channels(NB_CANAUX);
+        sdl2::mixer::reserve_channels(NB_CANAUX_COUCHES);
         let mixer_ctx: Sdl2MixerContext = sdl2::mixer::init(sdl2::mixer::InitFlag::MP3)?;
 
         {
@@ -41,19 +51,23 @@ impl<'m> MusicFactory<'m> {
             .collect::<HashMap<&str, Music>>();
 
 
-        let sound_arme_path: &Path = Path::new("assets/sounds/arme.mp3");
-        let sound_arme = sdl2::mixer::Chunk::from_file(sound_arme_path)?;
+        let sound_defs = SoundDef::load_all(crate::core::musics::mixer::SOUNDS_PATH)?;
 
-        let sounds = [
-            ("arme", sound_arme)
-        ]
-            .into_iter()
-            .collect::<HashMap<&str, Chunk>>();
+        let sounds = sound_defs
+            .iter()
+            .map(|(id, def)| {
+                Chunk::from_file(Path::new(def.fichier.as_str())).map(|chunk| (id.clone(), chunk))
+            })
+            .collect::<Result<HashMap<String, Chunk>, String>>()?;
+
+        let playlists = Playlist::load_all(crate::core::musics::playlist::PLAYLISTS_PATH)?;
 
         Ok(
             Self {
                 musics,
                 sounds,
+                sound_defs,
+                playlists,
                 _context_mixer: mixer_ctx
             }
         )
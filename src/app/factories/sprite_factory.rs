@@ -58,6 +58,9 @@ impl<'a> SpriteFactory<'a> {
         let panel_path: &Path = Path::new("assets/sprites/panel/glassPanel.png");
         let panel: Texture<'a> = tc.load_texture(panel_path)?;
 
+        let pc_codeur_path: &Path = Path::new("assets/sprites/pc-codeur.png");
+        let pc_codeur: Texture<'a> = tc.load_texture(pc_codeur_path)?;
+
         let sprites: HashMap<&str, Texture> = [
             ("smiley", spite_smiley),
             ("poulet", poulet),
@@ -75,6 +78,7 @@ impl<'a> SpriteFactory<'a> {
             ("planete_2", planet_2),
             ("planete_3", planet_3),
             ("panel", panel),
+            ("pc_codeur", pc_codeur),
         ]
             .into_iter()
             .collect::<HashMap<&str, Texture>>();
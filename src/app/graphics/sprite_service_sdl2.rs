@@ -1,11 +1,13 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use sdl2::pixels::Color as ColorSdl;
 use sdl2::rect::{Point, Rect};
-use sdl2::render::WindowCanvas;
+use sdl2::render::{BlendMode, WindowCanvas};
 
 use crate::app::factories::sprite_factory::SpriteFactory;
 use crate::core::graphics::CanDrawSprite;
+use crate::core::graphics::models::color::Color;
 use crate::core::sdd::vecteur2d::Vecteur2D;
 
 pub struct SpriteServiceSdl2<'sf> {
@@ -34,4 +36,19 @@ impl<'sf> CanDrawSprite for SpriteServiceSdl2<'sf> {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    fn draw_rect(&mut self, position: Vecteur2D<i32>, size: Vecteur2D<u32>, color: Color) -> Result<(), String> {
+        let mut canvas = self.canvas.borrow_mut();
+
+        // la couleur de dessin sert aussi au clear de la frame suivante : on la restaure
+        let couleur_precedente = canvas.draw_color();
+        let color_sdl: ColorSdl = color.into();
+
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(color_sdl);
+        canvas.fill_rect(Rect::new(position.x, position.y, size.x, size.y))?;
+        canvas.set_draw_color(couleur_precedente);
+
+        Ok(())
+    }
+}
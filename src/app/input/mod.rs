@@ -1,11 +1,14 @@
 use std::collections::HashMap;
-use crate::core::input::CanManageInput;
+use crate::core::input::{CanManageInput, TextInputEvent};
 use crate::core::sdd::vecteur2d::Vecteur2D;
 
 pub struct InputServiceImpl {
     pub key_manager: HashMap<String, bool>,
     pub key_mouse: HashMap<String, bool>,
-    pub mouse_position: Vecteur2D<f32>
+    pub mouse_position: Vecteur2D<f32>,
+    pub text_events: Vec<TextInputEvent>,
+    pub text_input_active: bool,
+    pub mouse_wheel: i32
 }
 
 impl InputServiceImpl {
@@ -13,7 +16,10 @@ impl InputServiceImpl {
         Self {
             key_manager: HashMap::new(),
             key_mouse: HashMap::new(),
-            mouse_position: Vecteur2D::new(0f32, 0f32)
+            mouse_position: Vecteur2D::new(0f32, 0f32),
+            text_events: vec![],
+            text_input_active: false,
+            mouse_wheel: 0
         }
     }
 }
@@ -90,4 +96,39 @@ impl CanManageInput for InputServiceImpl {
     fn get_mouse_position(&self) -> Vecteur2D<f32> {
         self.mouse_position.clone()
     }
+
+    fn set_text_input_active(&mut self, active: bool) {
+        self.text_input_active = active;
+        self.text_events.clear();
+    }
+
+    fn is_text_input_active(&self) -> bool {
+        self.text_input_active
+    }
+
+    fn push_text_input(&mut self, event: TextInputEvent) {
+        if self.text_input_active {
+            self.text_events.push(event);
+        }
+    }
+
+    fn text_input(&self) -> Vec<TextInputEvent> {
+        self.text_events.clone()
+    }
+
+    fn clear_text_input(&mut self) {
+        self.text_events.clear();
+    }
+
+    fn push_mouse_wheel(&mut self, crans: i32) {
+        self.mouse_wheel += crans;
+    }
+
+    fn mouse_wheel(&self) -> i32 {
+        self.mouse_wheel
+    }
+
+    fn clear_mouse_wheel(&mut self) {
+        self.mouse_wheel = 0;
+    }
 }
\ No newline at end of file
@@ -1,31 +1,339 @@
 use std::cell::RefCell;
 use std::rc::Rc;
-use crate::app::factories::music_factory::MusicFactory;
-use crate::core::musics::CanPlayMusic;
+use std::time::Instant;
+
+use crate::app::factories::music_factory::{MusicFactory, NB_CANAUX, NB_CANAUX_COUCHES};
+use crate::core::musics::{Bus, CanPlayMusic};
+use crate::core::musics::mixer::{BusVolumes, Mixer, Voix};
+use crate::core::musics::playlist::Playlist;
+use crate::core::musics::spatial::spatialiser;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+const LARGEUR_PANORAMIQUE: f32 = 400.0; // demi largeur de la fenetre
+const VITESSE_INTENSITE: f32 = 0.5; // variation max de l'intensite par seconde
+const VOLUME_MAX_COUCHE: i32 = 128;
+
+struct PlaylistEnCours {
+    nom: String,
+    playlist: Playlist,
+    index: usize
+}
 
 pub struct MusicServiceImpl<'mf> {
-    pub music_factory: Rc<RefCell<MusicFactory<'mf>>>
+    pub music_factory: Rc<RefCell<MusicFactory<'mf>>>,
+    pub mixer: Mixer,
+    volume_musique: i32,
+    volume_musique_applique: Option<i32>, // dernier volume donne a sdl, None avant la premiere musique
+    ecouteur: Vecteur2D<f32>,
+    piste_courante: Option<String>,
+    prochaine_piste: Option<(String, i32, i32)>, // id, volume, fondu entrant (ms)
+    playlist: Option<PlaylistEnCours>,
+    en_pause: bool,
+    intensite: f32,
+    intensite_cible: f32,
+    debut: Instant
+}
+
+impl<'mf> MusicServiceImpl<'mf> {
+    pub fn new(music_factory: Rc<RefCell<MusicFactory<'mf>>>, bus_volumes: BusVolumes) -> Self {
+        let definitions = music_factory.borrow().sound_defs.clone();
+
+        Self {
+            music_factory,
+            mixer: Mixer::new(bus_volumes, (NB_CANAUX - NB_CANAUX_COUCHES) as usize, definitions),
+            volume_musique: 0,
+            volume_musique_applique: None,
+            ecouteur: Vecteur2D::new(0.0, 0.0),
+            piste_courante: None,
+            prochaine_piste: None,
+            playlist: None,
+            en_pause: false,
+            intensite: 0.0,
+            intensite_cible: 0.0,
+            debut: Instant::now()
+        }
+    }
+
+    // reapplique les volumes des bus a la musique et aux sons en cours
+    fn appliquer_bus(&mut self) {
+        self.appliquer_volume_musique();
+
+        self.mixer.pool.voix_du_bus(Bus::Master)
+            .into_iter()
+            .for_each(|(canal, voix)| {
+                Self::canal_sfx(canal).set_volume(self.mixer.volume_effectif(voix.bus, voix.volume));
+            });
+
+        self.appliquer_couches();
+    }
+
+    // le volume de la musique n'est transmis a sdl que s'il a change (bus ou volume de la piste)
+    fn appliquer_volume_musique(&mut self) {
+        let volume = self.mixer.volume_effectif(Bus::Music, self.volume_musique);
+
+        if self.volume_musique_applique != Some(volume) {
+            sdl2::mixer::Music::set_volume(volume);
+            self.volume_musique_applique = Some(volume);
+        }
+    }
+
+    fn appliquer_couches(&self) {
+        if let Some(en_cours) = &self.playlist {
+            en_cours.playlist.couches
+                .iter()
+                .take(NB_CANAUX_COUCHES as usize)
+                .enumerate()
+                .for_each(|(canal, couche)| {
+                    let volume = (VOLUME_MAX_COUCHE as f32 * couche.gain(self.intensite)) as i32;
+                    sdl2::mixer::Channel(canal as i32).set_volume(self.mixer.volume_effectif(Bus::Music, volume));
+                });
+        }
+    }
+
+    fn arreter_couches(&self) {
+        (0..NB_CANAUX_COUCHES).for_each(|canal| sdl2::mixer::Channel(canal).halt());
+    }
+
+    fn demarrer_couches(&self, playlist: &Playlist) -> Result<(), String> {
+        let factory = self.music_factory.borrow();
+
+        playlist.couches
+            .iter()
+            .take(NB_CANAUX_COUCHES as usize)
+            .enumerate()
+            .try_for_each(|(canal, couche)| {
+                let son = factory.sounds.get(couche.son.as_str())
+                    .ok_or(format!("couche de musique : son '{}' inconnu", couche.son))?;
+                let channel = sdl2::mixer::Channel(canal as i32);
+                channel.set_volume(0);
+                channel.play(son, -1).map(|_| ())
+            })
+    }
+
+    // les canaux du pool d'effets sonores suivent ceux reserves aux couches
+    fn canal_sfx(canal: usize) -> sdl2::mixer::Channel {
+        sdl2::mixer::Channel(canal as i32 + NB_CANAUX_COUCHES)
+    }
+
+    fn boucles_playlist(&self) -> i32 {
+        match &self.playlist {
+            Some(en_cours) if en_cours.playlist.pistes.len() > 1 => 1,
+            _ => -1
+        }
+    }
 }
 
 impl<'mf> CanPlayMusic for MusicServiceImpl<'mf> {
-    fn play(&self, id: &str, volume: i32) -> Result<(), String> {
-        sdl2::mixer::Music::set_volume(volume);
+    fn play(&mut self, id: &str, volume: i32) -> Result<(), String> {
+        self.fade_in(id, volume, 0)
+    }
+
+    fn fade_in(&mut self, id: &str, volume: i32, duree_ms: i32) -> Result<(), String> {
+        self.volume_musique = volume;
+        self.appliquer_volume_musique();
+
+        let boucles = self.boucles_playlist();
+
+        self.music_factory.borrow().musics.get(id)
+            .map(|music| music.fade_in(boucles, duree_ms))
+            .unwrap_or(Err(format!("musique : '{}' inconnue", id).to_string()))?;
+
+        self.piste_courante = Some(id.to_string());
+        self.prochaine_piste = None;
+        self.en_pause = false;
+        Ok(())
+    }
+
+    fn fade_out(&mut self, duree_ms: i32) -> Result<(), String> {
+        self.prochaine_piste = None;
+        sdl2::mixer::Music::fade_out(duree_ms)
+    }
+
+    fn crossfade(&mut self, id: &str, volume: i32, duree_ms: i32) -> Result<(), String> {
+        let deja_en_cours = self.piste_courante.as_deref() == Some(id) && sdl2::mixer::Music::is_playing();
+
+        if deja_en_cours {
+            self.volume_musique = volume;
+            self.appliquer_bus();
+            Ok(())
+        } else if sdl2::mixer::Music::is_playing() && !self.en_pause {
+            sdl2::mixer::Music::fade_out(duree_ms / 2)?;
+            self.prochaine_piste = Some((id.to_string(), volume, duree_ms / 2));
+            Ok(())
+        } else {
+            self.fade_in(id, volume, duree_ms / 2)
+        }
+    }
 
-        self.music_factory.borrow().musics.get(id).map(|music| music.play(-1))
-            .map(|_| Ok(()))
-            .unwrap_or(Err(format!("musique : '{}' inconnue", id).to_string()))
+    fn pause(&mut self) {
+        sdl2::mixer::Music::pause();
+        self.en_pause = true;
     }
 
-    fn play_sound(&self, id: &str, volume: i32) -> Result<(), String> {
-        self.music_factory.borrow().sounds.get(id).map(|sound| {
-            sdl2::mixer::Channel(1).set_volume(volume);
-            sdl2::mixer::Channel(1).play(sound, 0).map(|_| ())
-        })
-            .unwrap_or(Err(format!("erreur lors de la lecture du son : {}", id)))
+    fn resume(&mut self) {
+        sdl2::mixer::Music::resume();
+        self.en_pause = false;
     }
 
-    fn stop(&self) -> Result<(), String> {
+    fn current_track(&self) -> Option<String> {
+        self.piste_courante.clone()
+    }
+
+    fn play_playlist(&mut self, nom: &str) -> Result<(), String> {
+        let (nom_resolu, playlist) = {
+            let factory = self.music_factory.borrow();
+            let nom_resolu = match nom.rsplit_once('.') {
+                Some((prefixe, _)) if !factory.playlists.contains_key(nom) => prefixe,
+                _ => nom
+            };
+            let playlist = factory.playlists.get(nom_resolu)
+                .cloned()
+                .ok_or(format!("playlist : '{}' inconnue", nom))?;
+            (nom_resolu.to_string(), playlist)
+        };
+
+        if self.playlist.as_ref().map(|en_cours| en_cours.nom == nom_resolu).unwrap_or(false) {
+            return Ok(());
+        }
+
+        // la piste en cours continue si elle fait partie de la nouvelle playlist
+        let index_courant = self.piste_courante.as_ref()
+            .and_then(|piste| playlist.pistes.iter().position(|p| p == piste))
+            .filter(|_| sdl2::mixer::Music::is_playing() && self.prochaine_piste.is_none());
+
+        self.arreter_couches();
+        self.demarrer_couches(&playlist)?;
+
+        self.playlist = Some(
+            PlaylistEnCours {
+                nom: nom_resolu,
+                playlist: playlist.clone(),
+                index: index_courant.unwrap_or(0)
+            }
+        );
+
+        match (index_courant, playlist.pistes.first()) {
+            (Some(_), _) => {
+                self.volume_musique = playlist.volume;
+                self.appliquer_bus();
+                Ok(())
+            }
+            (None, Some(premiere)) => self.crossfade(premiere.as_str(), playlist.volume, playlist.fondu_ms),
+            (None, None) => self.fade_out(playlist.fondu_ms)
+        }
+    }
+
+    fn set_intensity(&mut self, intensite: f32) {
+        self.intensite_cible = intensite.clamp(0.0, 1.0);
+    }
+
+    fn update(&mut self, dt: f32) -> Result<(), String> {
+        let ecart = self.intensite_cible - self.intensite;
+        self.intensite += ecart.clamp(-VITESSE_INTENSITE * dt, VITESSE_INTENSITE * dt);
+        self.appliquer_couches();
+
+        if self.en_pause || sdl2::mixer::Music::is_playing() {
+            return Ok(());
+        }
+
+        // le fondu sortant est termine : on lance la piste suivante
+        if let Some((id, volume, duree_ms)) = self.prochaine_piste.take() {
+            return self.fade_in(id.as_str(), volume, duree_ms);
+        }
+
+        let suivante = self.playlist.as_mut().and_then(|en_cours| {
+            en_cours.index = en_cours.playlist.piste_suivante(en_cours.index);
+            en_cours.playlist.pistes.get(en_cours.index)
+                .map(|piste| (piste.clone(), en_cours.playlist.volume, en_cours.playlist.fondu_ms))
+        });
+
+        match suivante {
+            Some((id, volume, duree_ms)) => self.fade_in(id.as_str(), volume, duree_ms / 2),
+            None => Ok(())
+        }
+    }
+
+    fn play_sound(&mut self, id: &str, volume: i32, position: Option<Vecteur2D<f32>>) -> Result<(), String> {
+        let def = self.mixer.definitions.get(id)
+            .cloned()
+            .ok_or(format!("erreur lors de la lecture du son : {}", id))?;
+
+        let spatialisation = match position {
+            Some(source) => match spatialiser(&self.ecouteur, &source, def.distance_max, LARGEUR_PANORAMIQUE) {
+                Some(spatialisation) => Some(spatialisation),
+                None => return Ok(()) // trop loin pour etre entendu
+            },
+            None => None
+        };
+
+        let maintenant = self.debut.elapsed().as_secs_f32();
+
+        if !self.mixer.cooldown_ok(id, maintenant) {
+            return Ok(());
+        }
+
+        let voix = Voix {
+            id: id.to_string(),
+            bus: def.bus,
+            volume,
+            priorite: def.priorite,
+            debut: maintenant
+        };
+
+        // aucune voix disponible : le son est simplement ignore
+        let canal = match self.mixer.pool.allouer(voix, |canal| !Self::canal_sfx(canal).is_playing()) {
+            Some(canal) => canal,
+            None => return Ok(())
+        };
+
+        let factory = self.music_factory.borrow();
+        let sound = factory.sounds.get(id)
+            .ok_or(format!("erreur lors de la lecture du son : {}", id))?;
+
+        let channel = Self::canal_sfx(canal);
+        channel.halt();
+        channel.set_volume(self.mixer.volume_effectif(def.bus, volume));
+        match spatialisation {
+            Some(spatialisation) => {
+                channel.set_panning(spatialisation.gauche, spatialisation.droite)?;
+                channel.set_distance(spatialisation.distance)?;
+            }
+            None => {
+                channel.unset_panning()?;
+                channel.unset_distance()?;
+            }
+        }
+        channel.play(sound, 0).map(|_| ())
+    }
+
+    fn set_listener(&mut self, position: Vecteur2D<f32>) {
+        self.ecouteur = position;
+    }
+
+    fn stop(&mut self) -> Result<(), String> {
         sdl2::mixer::Music::halt();
+        self.arreter_couches();
+        self.piste_courante = None;
+        self.prochaine_piste = None;
+        self.playlist = None;
         Ok(())
     }
-}
\ No newline at end of file
+
+    fn set_bus_volume(&mut self, bus: Bus, volume: f32) {
+        self.mixer.bus.set_volume(bus, volume);
+        self.appliquer_bus();
+    }
+
+    fn get_bus_volume(&self, bus: Bus) -> f32 {
+        self.mixer.bus.volume(bus)
+    }
+
+    fn set_bus_muted(&mut self, bus: Bus, muted: bool) {
+        self.mixer.bus.set_muted(bus, muted);
+        self.appliquer_bus();
+    }
+
+    fn is_bus_muted(&self, bus: Bus) -> bool {
+        self.mixer.bus.is_muted(bus)
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+// format de donnees tres simple :
+//   # commentaire
+//   [section]
+//   cle = valeur
+// les cles peuvent etre repetees (ex : lignes d'un prefab), l'ordre est conserve
+
+#[derive(Clone, Debug, Default)]
+pub struct IniSection {
+    pub nom: String,
+    pub entrees: Vec<(String, String)>
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct IniFile {
+    pub sections: Vec<IniSection>
+}
+
+impl IniSection {
+    pub fn new(nom: &str) -> Self {
+        Self {
+            nom: nom.to_string(),
+            entrees: vec![]
+        }
+    }
+
+    pub fn get(&self, cle: &str) -> Option<&str> {
+        self.entrees.iter()
+            .rev()
+            .find(|(k, _)| k == cle)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn get_all(&self, cle: &str) -> Vec<&str> {
+        self.entrees.iter()
+            .filter(|(k, _)| k == cle)
+            .map(|(_, v)| v.as_str())
+            .collect::<Vec<_>>()
+    }
+
+    pub fn get_or<T: FromStr>(&self, cle: &str, defaut: T) -> T {
+        self.get(cle)
+            .and_then(|valeur| valeur.parse::<T>().ok())
+            .unwrap_or(defaut)
+    }
+
+    pub fn get_list(&self, cle: &str) -> Vec<String> {
+        self.get(cle)
+            .map(|valeur| {
+                valeur.split(',')
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn set<V: ToString>(&mut self, cle: &str, valeur: V) {
+        match self.entrees.iter_mut().find(|(k, _)| k == cle) {
+            Some((_, v)) => *v = valeur.to_string(),
+            None => self.push(cle, valeur)
+        }
+    }
+
+    pub fn push<V: ToString>(&mut self, cle: &str, valeur: V) {
+        self.entrees.push((cle.to_string(), valeur.to_string()));
+    }
+}
+
+impl IniFile {
+    pub fn parse(contenu: &str) -> Result<Self, String> {
+        let mut sections = vec![IniSection::new("")];
+
+        for (numero, ligne_brute) in contenu.lines().enumerate() {
+            let ligne = ligne_brute.trim();
+
+            if ligne.is_empty() || ligne.starts_with('#') {
+                continue;
+            }
+
+            if ligne.starts_with('[') {
+                if !ligne.ends_with(']') {
+                    return Err(format!("ligne {} : section mal fermee '{}'", numero + 1, ligne));
+                }
+                sections.push(IniSection::new(ligne[1..ligne.len() - 1].trim()));
+            } else {
+                let (cle, valeur) = ligne
+                    .split_once('=')
+                    .ok_or(format!("ligne {} : '=' attendu dans '{}'", numero + 1, ligne))?;
+
+                sections.last_mut()
+                    .expect("il y a toujours une section")
+                    .push(cle.trim(), valeur.trim());
+            }
+        }
+
+        Ok(Self { sections })
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contenu = fs::read_to_string(path)
+            .map_err(|err| format!("impossible de lire '{}' : {}", path, err))?;
+
+        Self::parse(contenu.as_str())
+            .map_err(|err| format!("{} : {}", path, err))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_string())
+            .map_err(|err| format!("impossible d'ecrire '{}' : {}", path, err))
+    }
+
+    pub fn section(&self, nom: &str) -> Option<&IniSection> {
+        self.sections.iter().find(|section| section.nom == nom)
+    }
+
+    pub fn section_mut(&mut self, nom: &str) -> &mut IniSection {
+        if let Some(index) = self.sections.iter().position(|section| section.nom == nom) {
+            &mut self.sections[index]
+        } else {
+            self.sections.push(IniSection::new(nom));
+            self.sections.last_mut().expect("section tout juste ajoutee")
+        }
+    }
+
+    pub fn sections_prefixees(&self, prefixe: &str) -> Vec<&IniSection> {
+        self.sections.iter()
+            .filter(|section| section.nom.starts_with(prefixe))
+            .collect::<Vec<_>>()
+    }
+}
+
+impl fmt::Display for IniFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for section in self.sections.iter() {
+            if section.nom.is_empty() && section.entrees.is_empty() {
+                continue;
+            }
+            if !section.nom.is_empty() {
+                writeln!(f, "[{}]", section.nom)?;
+            }
+            for (cle, valeur) in section.entrees.iter() {
+                writeln!(f, "{} = {}", cle, valeur)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
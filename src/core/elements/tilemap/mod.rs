@@ -1,130 +1,153 @@
-use rand::Rng;
+use std::collections::HashMap;
+
 use crate::core::elements::tilemap::tile::{Tile, TileType};
+use crate::core::elements::tilemap::world_gen::WorldGenerator;
 use crate::core::sdd::vecteur2d::Vecteur2D;
 
 pub mod tile;
+pub mod noise;
+pub mod world_gen;
 
+// monde infini decoupe en chunks (une TileMap par chunk), generes autour du joueur a partir de la graine
 pub struct TileMapHudge {
-    pub tilemaps: Vec<Vec<TileMap>>,
+    pub chunks: HashMap<(i32, i32), TileMap>,
     pub tile_size: u32,
-    pub nb_tilemap_column: u32,
-    pub nb_tilemap_line: u32,
     pub width_one_tilemap: u32,
     pub height_one_tilemap: u32,
+    pub rayon_chargement: i32, // en chunks autour du centre
+    pub seed: u64,
+    pub generateur: Box<dyn WorldGenerator>,
+    pub modifications: HashMap<(i32, i32), TileType>, // tuiles modifiees depuis la generation (index global)
 }
 
 impl TileMapHudge {
-    pub fn new(w: u32, h: u32, tile_size: u32, width_one: u32, height_one: u32) -> Self {
-
-        let width_tilemap = width_one;
-        let height_tilemap = height_one;
-
-        let tilemaps = (0u32 .. h)
-            .into_iter()
-            .map(|current_line| {
-                (0u32 .. w)
-                    .into_iter()
-                    .map(|current_column| {
-
-                        TileMap::new(
-                            width_tilemap,
-                            height_tilemap,
-                            tile_size,
-                            Some(
-                                Vecteur2D::new(
-                                    current_column * width_tilemap,
-                                    current_line * height_tilemap
-                                )
-                            )
-                        )
-
-                    })
-                    .collect::<Vec<TileMap>>()
-            })
-            .collect::<Vec<Vec<TileMap>>>();
+    pub fn new(tile_size: u32, rayon_chargement: i32, seed: u64, generateur: Box<dyn WorldGenerator>) -> Self {
+        let taille_chunk = generateur.taille_chunk();
 
         Self {
-            tilemaps,
+            chunks: HashMap::new(),
             tile_size,
-            nb_tilemap_column: w,
-            nb_tilemap_line: h,
-            width_one_tilemap: width_tilemap,
-            height_one_tilemap: height_tilemap
+            width_one_tilemap: taille_chunk.x,
+            height_one_tilemap: taille_chunk.y,
+            rayon_chargement,
+            seed,
+            generateur,
+            modifications: HashMap::new()
         }
     }
 
-    pub fn get_tilemap_index_from_position(&self, position: &Vecteur2D<f32>) -> Vecteur2D<u32> {
-        let index_tile_x = (position.x / self.tile_size as f32).floor() as u32;
-        let index_tile_y = (position.y / self.tile_size as f32).floor() as u32;
+    // charge les chunks proches du centre et decharge ceux qui sont trop loin
+    // (marge d'un chunk pour ne pas regenerer en boucle a la frontiere)
+    pub fn update_chunks(&mut self, centre: &Vecteur2D<f32>) {
+        let index_centre = self.get_tilemap_index_from_position(centre);
+        let rayon = self.rayon_chargement;
 
-        let index_tilemap_x = index_tile_x / self.width_one_tilemap;
-        let index_tilemap_y = index_tile_y / self.height_one_tilemap;
+        self.chunks.retain(|(x, y), _| {
+            (x - index_centre.x).abs() <= rayon + 1 && (y - index_centre.y).abs() <= rayon + 1
+        });
 
-        Vecteur2D::new(index_tilemap_x, index_tilemap_y)
-    }
-
-    pub fn get_tilemap_from_index(&self, index: &Vecteur2D<u32>) -> &TileMap {
-       self.tilemaps.get(index.y as usize).unwrap().get(index.x as usize).unwrap()
+        (-rayon..=rayon).for_each(|dy| {
+            (-rayon..=rayon).for_each(|dx| {
+                self.ensure_chunk(&Vecteur2D::new(index_centre.x + dx, index_centre.y + dy));
+            })
+        });
     }
 
-    pub fn get_tilemap_from_position(&self, position: &Vecteur2D<f32>) -> Option<&TileMap> {
-        let index_curent_tilemap = self.get_tilemap_index_from_position(position);
-
-        match (index_curent_tilemap.x as i32, index_curent_tilemap.y as i32) {
-            (x, y) if self.indexes_tilemap_valid(x, y) => {
+    pub fn ensure_chunk(&mut self, index: &Vecteur2D<i32>) {
+        if self.chunks.contains_key(&(index.x, index.y)) {
+            return;
+        }
 
-                let line = self.tilemaps.get(y as usize).unwrap();
+        let origine = Vecteur2D::new(
+            index.x * self.width_one_tilemap as i32,
+            index.y * self.height_one_tilemap as i32
+        );
+        let types = self.generateur.generate_chunk(index);
+
+        let mut tilemap = TileMap::new(
+            self.width_one_tilemap,
+            self.height_one_tilemap,
+            self.tile_size,
+            Some(origine.clone()),
+            |x, y| types[(y - origine.y) as usize][(x - origine.x) as usize].clone()
+        );
 
-                let tilemap = line.get(x as usize).unwrap();
+        // un chunk regenere retrouve ses tuiles modifiees
+        tilemap.tiles
+            .iter_mut()
+            .flatten()
+            .for_each(|tile| {
+                if let Some(r#type) = self.modifications.get(&(tile.pos.x as i32, tile.pos.y as i32)) {
+                    tile.r#type = r#type.clone();
+                }
+            });
+
+        self.chunks.insert((index.x, index.y), tilemap);
+    }
 
-                Some(tilemap)
-            },
-            _ => None
-        }
+    pub fn get_tile_index_from_position(&self, position: &Vecteur2D<f32>) -> Vecteur2D<i32> {
+        Vecteur2D::new(
+            (position.x / self.tile_size as f32).floor() as i32,
+            (position.y / self.tile_size as f32).floor() as i32
+        )
     }
 
-    pub fn get_tilemap_from_position_mut(&mut self, position: &Vecteur2D<f32>) -> Option<&mut TileMap> {
-        let index_curent_tilemap = self.get_tilemap_index_from_position(position);
+    pub fn get_tilemap_index_from_tile_index(&self, index_tile: &Vecteur2D<i32>) -> Vecteur2D<i32> {
+        Vecteur2D::new(
+            index_tile.x.div_euclid(self.width_one_tilemap as i32),
+            index_tile.y.div_euclid(self.height_one_tilemap as i32)
+        )
+    }
 
-        match (index_curent_tilemap.x as i32, index_curent_tilemap.y as i32) {
-            (x, y) if self.indexes_tilemap_valid(x, y) => {
+    pub fn get_tilemap_index_from_position(&self, position: &Vecteur2D<f32>) -> Vecteur2D<i32> {
+        self.get_tilemap_index_from_tile_index(&self.get_tile_index_from_position(position))
+    }
 
-                let line = self.tilemaps.get_mut(y as usize).unwrap();
+    pub fn get_tilemap_from_index(&self, index: &Vecteur2D<i32>) -> Option<&TileMap> {
+        self.chunks.get(&(index.x, index.y))
+    }
 
-                let tilemap = line.get_mut(x as usize).unwrap();
+    pub fn get_tilemap_from_position(&self, position: &Vecteur2D<f32>) -> Option<&TileMap> {
+        self.get_tilemap_from_index(&self.get_tilemap_index_from_position(position))
+    }
 
-                Some(tilemap)
-            },
-            _ => None
-        }
+    pub fn get_tilemap_from_position_mut(&mut self, position: &Vecteur2D<f32>) -> Option<&mut TileMap> {
+        let index = self.get_tilemap_index_from_position(position);
+        self.chunks.get_mut(&(index.x, index.y))
     }
 
-    pub fn indexes_tilemap_valid(&self, x: i32, y: i32) -> bool {
-        x >= 0 && y >= 0 && x < self.nb_tilemap_column as i32 && y < self.nb_tilemap_line as i32
+    // None si le chunk n'est pas charge
+    pub fn get_tile_at(&self, index_tile: &Vecteur2D<i32>) -> Option<&Tile> {
+        let index_tm = self.get_tilemap_index_from_tile_index(index_tile);
+
+        self.get_tilemap_from_index(&index_tm)
+            .and_then(|tilemap| {
+                tilemap.get_tile_at(
+                    &Vecteur2D::new(
+                        index_tile.x.rem_euclid(self.width_one_tilemap as i32) as u32,
+                        index_tile.y.rem_euclid(self.height_one_tilemap as i32) as u32
+                    )
+                )
+            })
     }
 
     pub fn get_tile_from_position(&self, position: &Vecteur2D<f32>) -> Option<&Tile> {
-        let index_x = (position.x / self.tile_size as f32).floor() as i32;
-        let index_y = (position.y / self.tile_size as f32).floor() as i32;
-
-        let index_tm = self.get_tilemap_index_from_position(position);
+        self.get_tile_at(&self.get_tile_index_from_position(position))
+    }
 
-        let real_index = Vecteur2D::new(
-            index_x as u32 - (index_tm.x * self.width_one_tilemap),
-            index_y as u32 - (index_tm.y * self.height_one_tilemap),
+    // modifie une tuile (index global) et garde la trace du changement pour la sauvegarde
+    // et pour les chunks regeneres
+    pub fn set_tile(&mut self, index: &Vecteur2D<i32>, r#type: TileType) {
+        let index_tm = self.get_tilemap_index_from_tile_index(index);
+        let local = Vecteur2D::new(
+            index.x.rem_euclid(self.width_one_tilemap as i32),
+            index.y.rem_euclid(self.height_one_tilemap as i32)
         );
 
-        // println!("real index = {:?}", real_index);
-        // println!("tm index from pos = {:?}", index_tm);
-
-        let tilemap_opt = self.get_tilemap_from_position(position);
-
-        match tilemap_opt {
-            Some(tilemap) => {
-                tilemap.get_tile_at(&real_index)
-            }
-            _ => None
+        if let Some(tilemap) = self.chunks.get_mut(&(index_tm.x, index_tm.y)) {
+            tilemap.tiles[local.y as usize][local.x as usize].r#type = r#type.clone();
         }
+        self.modifications.insert((index.x, index.y), r#type);
     }
 }
 
@@ -135,44 +158,25 @@ pub struct TileMap {
 }
 
 impl TileMap {
-    pub fn new(w: u32, h: u32, tile_size: u32, from_coord: Option<Vecteur2D<u32>>) -> Self {
+    // biome : type de tuile a partir de l'index global (x, y) de la tuile
+    pub fn new<F>(w: u32, h: u32, tile_size: u32, from_coord: Option<Vecteur2D<i32>>, biome: F) -> Self
+        where F: Fn(i32, i32) -> TileType
+    {
 
         let cood_base = from_coord.unwrap_or(Vecteur2D::new(0, 0));
 
-        let mut rand = rand::thread_rng();
-
-        let is_other_than_grass = rand.gen_range(0..3) == 0;
-
-        let type_de_biome = if !is_other_than_grass {
-            TileType::Herbe
-        } else {
-            let prob = rand.gen_range(0..10);
-
-            if prob % 2 == 0 {
-                TileType::Sand
-            } else if prob % 3 == 0 {
-                TileType::Snow
-            } else {
-                TileType::Goo
-            }
-        };
-
         let tiles = (0u32 .. h)
             .into_iter()
             .map(|current_line| {
                 (0u32 .. w)
                     .into_iter()
                     .map(|current_column| {
+                        let x = current_column as i32 + cood_base.x;
+                        let y = current_line as i32 + cood_base.y;
+
                         Tile {
-                            pos: Vecteur2D::new(
-                                (current_column + cood_base.x) as f32,
-                                (current_line + cood_base.y) as f32
-                            ),
-                            r#type: if current_column == 0 && current_line % 3 == 0 && current_column % 3 == 0 {
-                                TileType::Mur
-                            } else {
-                                type_de_biome.clone()
-                            }
+                            pos: Vecteur2D::new(x as f32, y as f32),
+                            r#type: biome(x, y)
                         }
                     })
                     .collect::<Vec<Tile>>()
@@ -182,22 +186,16 @@ impl TileMap {
         Self { tiles, tile_size }
     }
 
+    // position en pixels dans le monde : les tuiles gardent leur index global, le chunk est decale de sa premiere tuile
     pub fn get_tile_from_position(&self, position: &Vecteur2D<f32>) -> Option<Tile> {
-        let index_x = (position.x / self.tile_size as f32).floor() as i32;
-        let index_y = (position.y / self.tile_size as f32).floor() as i32;
-
-        match (index_x, index_y) {
-            (x, y) if self.indexes_valid(x, y) => {
-                // fixme clean le code
-                let line = self.tiles
-                    .get(y as usize)
-                    .unwrap();
-
-                let tile = line.get(x as usize).unwrap().clone();
+        let origine = &self.tiles.first()?.first()?.pos;
+        let index_x = (position.x / self.tile_size as f32).floor() as i32 - origine.x as i32;
+        let index_y = (position.y / self.tile_size as f32).floor() as i32 - origine.y as i32;
 
-                Some(tile)
-            },
-            _ => None
+        if self.indexes_valid(index_x, index_y) {
+            self.get_tile_at(&Vecteur2D::new(index_x as u32, index_y as u32)).cloned()
+        } else {
+            None
         }
     }
 
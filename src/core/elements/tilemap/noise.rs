@@ -0,0 +1,39 @@
+use crate::core::sdd::graine::melanger_graine;
+
+// valeur pseudo aleatoire dans [0, 1) attachee a un point entier de la grille
+fn valeur_grille(seed: u64, x: i32, y: i32) -> f32 {
+    (melanger_graine(seed, x, y) >> 40) as f32 / (1u64 << 24) as f32
+}
+
+fn lisser(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+// bruit de valeur : interpolation lissee entre les 4 points de grille voisins, resultat dans [0, 1)
+pub fn bruit_valeur(seed: u64, x: f32, y: f32) -> f32 {
+    let x0 = x.floor() as i32;
+    let y0 = y.floor() as i32;
+    let tx = lisser(x - x0 as f32);
+    let ty = lisser(y - y0 as f32);
+
+    let haut = valeur_grille(seed, x0, y0) * (1.0 - tx) + valeur_grille(seed, x0 + 1, y0) * tx;
+    let bas = valeur_grille(seed, x0, y0 + 1) * (1.0 - tx) + valeur_grille(seed, x0 + 1, y0 + 1) * tx;
+
+    haut * (1.0 - ty) + bas * ty
+}
+
+// somme de plusieurs octaves de bruit de valeur, normalisee dans [0, 1)
+pub fn bruit_fractal(seed: u64, x: f32, y: f32, octaves: u32, persistance: f32, lacunarite: f32) -> f32 {
+    let (somme, amplitude_totale, _, _) = (0..octaves.max(1))
+        .fold((0.0, 0.0, 1.0, 1.0), |(somme, total, amplitude, frequence), octave| {
+            let graine_octave = melanger_graine(seed, octave as i32, 0);
+            (
+                somme + bruit_valeur(graine_octave, x * frequence, y * frequence) * amplitude,
+                total + amplitude,
+                amplitude * persistance,
+                frequence * lacunarite
+            )
+        });
+
+    somme / amplitude_totale
+}
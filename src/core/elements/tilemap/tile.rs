@@ -7,7 +7,28 @@ pub enum TileType {
     Sand,
     Snow,
     Goo,
-    Wood
+    Wood,
+    Porte
+}
+
+impl TileType {
+    pub fn code(&self) -> &'static str {
+        match self {
+            TileType::Herbe => "herbe",
+            TileType::Mur => "mur",
+            TileType::Sand => "sand",
+            TileType::Snow => "snow",
+            TileType::Goo => "goo",
+            TileType::Wood => "wood",
+            TileType::Porte => "porte"
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        [TileType::Herbe, TileType::Mur, TileType::Sand, TileType::Snow, TileType::Goo, TileType::Wood, TileType::Porte]
+            .into_iter()
+            .find(|r#type| r#type.code() == code)
+    }
 }
 
 #[derive(Clone)]
@@ -0,0 +1,59 @@
+use crate::core::elements::tilemap::noise::bruit_fractal;
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::world_gen::params::WorldGenParams;
+use crate::core::elements::tilemap::world_gen::WorldGenerator;
+use crate::core::sdd::graine::melanger_graine;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+const NIVEAU_MER: f32 = 0.5;
+const LARGEUR_PLAGE: f32 = 0.04;
+
+// archipel : une carte d'altitude decoupe des iles dans une mer de goo infranchissable
+pub struct Archipelago {
+    seed: u64,
+    params: WorldGenParams,
+    taille_chunk: Vecteur2D<u32>
+}
+
+impl Archipelago {
+    pub fn new(seed: u64, params: WorldGenParams, taille_chunk: Vecteur2D<u32>) -> Self {
+        Self { seed, params, taille_chunk }
+    }
+}
+
+impl WorldGenerator for Archipelago {
+    fn nom(&self) -> &str {
+        "archipel"
+    }
+
+    fn taille_chunk(&self) -> Vecteur2D<u32> {
+        self.taille_chunk.clone()
+    }
+
+    fn tuiles_bloquantes(&self) -> Vec<TileType> {
+        vec![TileType::Mur, TileType::Goo]
+    }
+
+    fn tile_at(&self, x: i32, y: i32) -> TileType {
+        let altitude = bruit_fractal(
+            melanger_graine(self.seed, 4, 0),
+            x as f32 * self.params.echelle,
+            y as f32 * self.params.echelle,
+            self.params.octaves,
+            self.params.persistance,
+            self.params.lacunarite
+        );
+
+        if altitude < NIVEAU_MER {
+            TileType::Goo
+        } else if altitude < NIVEAU_MER + LARGEUR_PLAGE {
+            TileType::Sand
+        } else {
+            // pas de marais sur les iles : le goo est reserve a la mer
+            match self.params.biome_at(self.seed, x, y) {
+                TileType::Goo => TileType::Herbe,
+                biome => biome
+            }
+        }
+    }
+}
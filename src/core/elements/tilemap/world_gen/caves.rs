@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::world_gen::params::WorldGenParams;
+use crate::core::elements::tilemap::world_gen::WorldGenerator;
+use crate::core::sdd::cache::CacheBorne;
+use crate::core::sdd::graine::melanger_graine;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+const REMPLISSAGE: f32 = 0.45; // proportion de murs avant lissage
+const ITERATIONS: i32 = 4;
+const NB_CHUNKS_EN_CACHE: usize = 64; // un chunk entier est genere pour lire une seule de ses tuiles
+
+type CacheChunks = CacheBorne<(i32, i32), Vec<Vec<TileType>>>;
+
+// grottes par automate cellulaire : le remplissage initial ne depend que de la position globale,
+// et chaque iteration consomme une tuile de marge autour de la zone calculee,
+// les chunks se raccordent donc exactement
+pub struct Caves {
+    seed: u64,
+    params: WorldGenParams,
+    taille_chunk: Vecteur2D<u32>,
+    chunks: RefCell<CacheChunks>
+}
+
+impl Caves {
+    pub fn new(seed: u64, params: WorldGenParams, taille_chunk: Vecteur2D<u32>) -> Self {
+        Self {
+            seed,
+            params,
+            taille_chunk,
+            chunks: RefCell::new(CacheBorne::new(NB_CHUNKS_EN_CACHE))
+        }
+    }
+
+    fn mur_initial(&self, x: i32, y: i32) -> bool {
+        let tirage = (melanger_graine(melanger_graine(self.seed, 5, 0), x, y) >> 40) as f32 / (1u64 << 24) as f32;
+        tirage < REMPLISSAGE
+    }
+
+    // murs de la zone [origine, origine + taille[, [ligne][colonne]
+    fn automate(&self, origine: &Vecteur2D<i32>, largeur: i32, hauteur: i32) -> Vec<Vec<bool>> {
+        let mut murs = (0..hauteur + 2 * ITERATIONS)
+            .map(|ligne| {
+                (0..largeur + 2 * ITERATIONS)
+                    .map(|colonne| self.mur_initial(origine.x - ITERATIONS + colonne, origine.y - ITERATIONS + ligne))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        (0..ITERATIONS).for_each(|_| {
+            let precedent = murs.clone();
+            let h = precedent.len() - 2;
+            let w = precedent[0].len() - 2;
+
+            murs = (1..=h)
+                .map(|ligne| {
+                    (1..=w)
+                        .map(|colonne| {
+                            let voisins = (ligne - 1..=ligne + 1)
+                                .flat_map(|l| (colonne - 1..=colonne + 1).map(move |c| (l, c)))
+                                .filter(|(l, c)| (*l, *c) != (ligne, colonne) && precedent[*l][*c])
+                                .count();
+
+                            voisins > 4 || (voisins == 4 && precedent[ligne][colonne])
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+        });
+
+        murs
+    }
+
+    fn tile_from_mur(&self, mur: bool, x: i32, y: i32) -> TileType {
+        if mur {
+            TileType::Mur
+        } else {
+            self.params.biome_at(self.seed, x, y)
+        }
+    }
+
+    fn generer_chunk(&self, index: &Vecteur2D<i32>) -> Vec<Vec<TileType>> {
+        let largeur = self.taille_chunk.x as i32;
+        let hauteur = self.taille_chunk.y as i32;
+        let origine = Vecteur2D::new(index.x * largeur, index.y * hauteur);
+
+        self.automate(&origine, largeur, hauteur)
+            .into_iter()
+            .enumerate()
+            .map(|(ligne, murs)| {
+                murs.into_iter()
+                    .enumerate()
+                    .map(|(colonne, mur)| self.tile_from_mur(mur, origine.x + colonne as i32, origine.y + ligne as i32))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+impl WorldGenerator for Caves {
+    fn nom(&self) -> &str {
+        "grottes"
+    }
+
+    fn taille_chunk(&self) -> Vecteur2D<u32> {
+        self.taille_chunk.clone()
+    }
+
+    fn tile_at(&self, x: i32, y: i32) -> TileType {
+        let largeur = self.taille_chunk.x as i32;
+        let hauteur = self.taille_chunk.y as i32;
+        let index = (x.div_euclid(largeur), y.div_euclid(hauteur));
+
+        let mut chunks = self.chunks.borrow_mut();
+        let chunk = chunks.get_or_insert_with(index, || self.generer_chunk(&Vecteur2D::new(index.0, index.1)));
+
+        chunk[y.rem_euclid(hauteur) as usize][x.rem_euclid(largeur) as usize].clone()
+    }
+
+    fn generate_chunk(&self, index: &Vecteur2D<i32>) -> Vec<Vec<TileType>> {
+        self.chunks.borrow_mut()
+            .get_or_insert_with((index.x, index.y), || self.generer_chunk(index))
+            .clone()
+    }
+}
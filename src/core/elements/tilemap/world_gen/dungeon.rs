@@ -0,0 +1,163 @@
+use std::cell::RefCell;
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::world_gen::WorldGenerator;
+use crate::core::sdd::cache::CacheBorne;
+use crate::core::sdd::graine::melanger_graine;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+const TAILLE_ZONE_MIN: i32 = 6;
+const PROFONDEUR_BSP: u32 = 3;
+const NB_CHUNKS_EN_CACHE: usize = 64; // un chunk entier est genere pour lire une seule de ses tuiles
+
+type CacheChunks = CacheBorne<(i32, i32), Vec<Vec<TileType>>>;
+
+// zone locale au chunk
+struct Zone {
+    x: i32,
+    y: i32,
+    largeur: i32,
+    hauteur: i32
+}
+
+// donjon : chaque chunk est decoupe en salles (BSP) reliees par des couloirs,
+// et ouvert au milieu de chacun de ses bords pour se raccorder aux chunks voisins
+pub struct Dungeon {
+    seed: u64,
+    taille_chunk: Vecteur2D<u32>,
+    chunks: RefCell<CacheChunks>
+}
+
+impl Dungeon {
+    pub fn new(seed: u64, taille_chunk: Vecteur2D<u32>) -> Self {
+        Self {
+            seed,
+            taille_chunk,
+            chunks: RefCell::new(CacheBorne::new(NB_CHUNKS_EN_CACHE))
+        }
+    }
+
+    fn decouper(&self, zone: Zone, profondeur: u32, rng: &mut StdRng, grille: &mut [Vec<TileType>]) -> Vecteur2D<i32> {
+        let coupe_verticale = zone.largeur >= zone.hauteur;
+        let taille = if coupe_verticale { zone.largeur } else { zone.hauteur };
+
+        if profondeur == 0 || taille < 2 * TAILLE_ZONE_MIN {
+            return Self::creuser_salle(&zone, rng, grille);
+        }
+
+        let coupe = rng.gen_range(TAILLE_ZONE_MIN..=taille - TAILLE_ZONE_MIN);
+        let (a, b) = if coupe_verticale {
+            (
+                Zone { x: zone.x, y: zone.y, largeur: coupe, hauteur: zone.hauteur },
+                Zone { x: zone.x + coupe, y: zone.y, largeur: zone.largeur - coupe, hauteur: zone.hauteur }
+            )
+        } else {
+            (
+                Zone { x: zone.x, y: zone.y, largeur: zone.largeur, hauteur: coupe },
+                Zone { x: zone.x, y: zone.y + coupe, largeur: zone.largeur, hauteur: zone.hauteur - coupe }
+            )
+        };
+
+        let centre_a = self.decouper(a, profondeur - 1, rng, grille);
+        let centre_b = self.decouper(b, profondeur - 1, rng, grille);
+        Self::creuser_couloir(&centre_a, &centre_b, grille);
+
+        centre_a
+    }
+
+    // une tuile de marge dans la zone pour garder un mur entre deux salles
+    fn creuser_salle(zone: &Zone, rng: &mut StdRng, grille: &mut [Vec<TileType>]) -> Vecteur2D<i32> {
+        let largeur = rng.gen_range(3..=zone.largeur - 2);
+        let hauteur = rng.gen_range(3..=zone.hauteur - 2);
+        let x = zone.x + rng.gen_range(1..=zone.largeur - 1 - largeur);
+        let y = zone.y + rng.gen_range(1..=zone.hauteur - 1 - hauteur);
+
+        (y..y + hauteur).for_each(|ligne| {
+            (x..x + largeur).for_each(|colonne| Self::creuser(colonne, ligne, grille))
+        });
+
+        Vecteur2D::new(x + largeur / 2, y + hauteur / 2)
+    }
+
+    // couloir en L de deux tuiles de large, pour laisser passer le joueur
+    fn creuser_couloir(de: &Vecteur2D<i32>, vers: &Vecteur2D<i32>, grille: &mut [Vec<TileType>]) {
+        (de.x.min(vers.x)..=de.x.max(vers.x)).for_each(|x| {
+            Self::creuser(x, de.y, grille);
+            Self::creuser(x, de.y + 1, grille);
+        });
+        (de.y.min(vers.y)..=de.y.max(vers.y)).for_each(|y| {
+            Self::creuser(vers.x, y, grille);
+            Self::creuser(vers.x + 1, y, grille);
+        });
+    }
+
+    // salles et couloirs du chunk, puis ses portes vers les chunks voisins
+    fn generer_chunk(&self, index: &Vecteur2D<i32>) -> Vec<Vec<TileType>> {
+        let largeur = self.taille_chunk.x as i32;
+        let hauteur = self.taille_chunk.y as i32;
+        let mut rng = StdRng::seed_from_u64(melanger_graine(self.seed, index.x, index.y));
+        let mut grille = vec![vec![TileType::Mur; largeur as usize]; hauteur as usize];
+
+        let centre = self.decouper(
+            Zone { x: 0, y: 0, largeur, hauteur },
+            PROFONDEUR_BSP,
+            &mut rng,
+            &mut grille
+        );
+
+        // portes (2x2) au milieu des bords : elles tombent face a face d'un chunk a l'autre
+        [
+            Vecteur2D::new(largeur / 2, 0),
+            Vecteur2D::new(largeur / 2, hauteur - 2),
+            Vecteur2D::new(0, hauteur / 2),
+            Vecteur2D::new(largeur - 2, hauteur / 2)
+        ]
+            .iter()
+            .for_each(|porte| {
+                Self::creuser_couloir(&centre, porte, &mut grille);
+                Self::creuser_couloir(porte, &Vecteur2D::new(porte.x, porte.y + 1), &mut grille);
+            });
+
+        grille
+    }
+
+    fn creuser(x: i32, y: i32, grille: &mut [Vec<TileType>]) {
+        if let Some(tile) = grille.get_mut(y as usize).and_then(|ligne| ligne.get_mut(x as usize)) {
+            *tile = TileType::Wood;
+        }
+    }
+}
+
+impl WorldGenerator for Dungeon {
+    fn nom(&self) -> &str {
+        "donjon"
+    }
+
+    fn tuile_creusee(&self, _x: i32, _y: i32) -> TileType {
+        TileType::Wood
+    }
+
+    fn taille_chunk(&self) -> Vecteur2D<u32> {
+        self.taille_chunk.clone()
+    }
+
+    fn tile_at(&self, x: i32, y: i32) -> TileType {
+        let largeur = self.taille_chunk.x as i32;
+        let hauteur = self.taille_chunk.y as i32;
+        let index = (x.div_euclid(largeur), y.div_euclid(hauteur));
+
+        let mut chunks = self.chunks.borrow_mut();
+        let chunk = chunks.get_or_insert_with(index, || self.generer_chunk(&Vecteur2D::new(index.0, index.1)));
+
+        chunk[y.rem_euclid(hauteur) as usize][x.rem_euclid(largeur) as usize].clone()
+    }
+
+    fn generate_chunk(&self, index: &Vecteur2D<i32>) -> Vec<Vec<TileType>> {
+        self.chunks.borrow_mut()
+            .get_or_insert_with((index.x, index.y), || self.generer_chunk(index))
+            .clone()
+    }
+}
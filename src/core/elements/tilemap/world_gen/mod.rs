@@ -0,0 +1,157 @@
+use rand::{Rng, RngCore};
+
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::world_gen::archipelago::Archipelago;
+use crate::core::elements::tilemap::world_gen::caves::Caves;
+use crate::core::elements::tilemap::world_gen::dungeon::Dungeon;
+use crate::core::elements::tilemap::world_gen::overworld::Overworld;
+use crate::core::elements::tilemap::world_gen::params::WorldGenParams;
+use crate::core::elements::tilemap::world_gen::prefab::{Prefab, PREFABS_PATH};
+use crate::core::elements::tilemap::world_gen::spawn::SpawnRules;
+use crate::core::elements::tilemap::world_gen::structures::Structures;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub mod params;
+pub mod overworld;
+pub mod caves;
+pub mod dungeon;
+pub mod archipelago;
+pub mod reachability;
+pub mod spawn;
+pub mod prefab;
+pub mod structures;
+
+// distance max (en tuiles) pour trouver une tuile franchissable autour d'un point voulu
+const RAYON_RECHERCHE: i32 = 40;
+
+// strategie de generation d'un monde : tuiles (par chunk), depart du joueur,
+// points d'apparition des ennemis et emplacement du vaisseau (index globaux de tuiles)
+pub trait WorldGenerator {
+    fn nom(&self) -> &str;
+
+    fn taille_chunk(&self) -> Vecteur2D<u32>;
+
+    // doit etre deterministe : une tuile ne depend que de la graine et de sa position
+    fn tile_at(&self, x: i32, y: i32) -> TileType;
+
+    fn tuiles_bloquantes(&self) -> Vec<TileType> {
+        vec![TileType::Mur]
+    }
+
+    // sol pose quand il faut creuser un passage (cf reachability)
+    fn tuile_creusee(&self, _x: i32, _y: i32) -> TileType {
+        TileType::Sand
+    }
+
+    // tuiles du chunk [ligne][colonne]
+    fn generate_chunk(&self, index: &Vecteur2D<i32>) -> Vec<Vec<TileType>> {
+        let taille = self.taille_chunk();
+        let origine = Vecteur2D::new(index.x * taille.x as i32, index.y * taille.y as i32);
+
+        (0..taille.y as i32)
+            .map(|ligne| {
+                (0..taille.x as i32)
+                    .map(|colonne| self.tile_at(origine.x + colonne, origine.y + ligne))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn est_franchissable(&self, x: i32, y: i32) -> bool {
+        !self.tuiles_bloquantes().contains(&self.tile_at(x, y))
+    }
+
+    // tuile franchissable la plus proche, cherchee par carres concentriques
+    fn sol_le_plus_proche(&self, autour: &Vecteur2D<i32>) -> Option<Vecteur2D<i32>> {
+        (0..=RAYON_RECHERCHE).find_map(|rayon| {
+            (-rayon..=rayon)
+                .flat_map(|dy| (-rayon..=rayon).map(move |dx| (dx, dy)))
+                .filter(|(dx, dy)| dx.abs() == rayon || dy.abs() == rayon)
+                .map(|(dx, dy)| Vecteur2D::new(autour.x + dx, autour.y + dy))
+                .find(|pos| self.est_franchissable(pos.x, pos.y))
+        })
+    }
+
+    fn player_start(&self) -> Vecteur2D<i32> {
+        let depart = Vecteur2D::new(2, 2);
+        self.sol_le_plus_proche(&depart).unwrap_or(depart)
+    }
+
+    // un des trois coins de la zone opposes au depart
+    fn exit_location(&self, zone_min: &Vecteur2D<i32>, zone_max: &Vecteur2D<i32>, rng: &mut dyn RngCore) -> Vecteur2D<i32> {
+        let coin = match rng.gen_range(0..3) {
+            0 => zone_max.clone(),
+            1 => Vecteur2D::new(zone_min.x, zone_max.y),
+            _ => Vecteur2D::new(zone_max.x, zone_min.y)
+        };
+
+        self.sol_le_plus_proche(&coin).unwrap_or(coin)
+    }
+
+    // tirages dans la zone filtres par les regles d'apparition : un ennemi qui ne trouve pas
+    // de place valide apres `regles.essais` tirages n'apparait pas
+    fn spawn_points(
+        &self,
+        zone_min: &Vecteur2D<i32>,
+        zone_max: &Vecteur2D<i32>,
+        depart: &Vecteur2D<i32>,
+        nombre: u32,
+        regles: &SpawnRules,
+        rng: &mut dyn RngCore
+    ) -> Vec<Vecteur2D<i32>> {
+        let bloquantes = self.tuiles_bloquantes();
+        let poids_max = regles.poids_max();
+        let mut points: Vec<Vecteur2D<i32>> = vec![];
+
+        (0..nombre).for_each(|_| {
+            let point = (0..regles.essais).find_map(|_| {
+                let pos = Vecteur2D::new(
+                    rng.gen_range(zone_min.x..zone_max.x),
+                    rng.gen_range(zone_min.y..zone_max.y)
+                );
+                let r#type = self.tile_at(pos.x, pos.y);
+
+                // plus le biome a de poids, plus le tirage a de chances d'etre garde
+                let tirage_garde = rng.gen::<f32>() * poids_max < regles.poids(&r#type);
+
+                if tirage_garde
+                    && regles.position_valide(&pos, &r#type, &bloquantes, depart)
+                    && regles.assez_espace(&pos, &points) {
+                    Some(pos)
+                } else {
+                    None
+                }
+            });
+
+            if let Some(point) = point {
+                points.push(point);
+            }
+        });
+
+        points
+    }
+}
+
+// le generateur est tire parmi ceux autorises pour le niveau, selon la graine du monde (la planete)
+pub fn choisir_generateur(params: WorldGenParams, seed: u64, taille_chunk: Vecteur2D<u32>) -> Result<Box<dyn WorldGenerator>, String> {
+    let nom = match params.generateurs.len() {
+        0 => "overworld".to_string(),
+        nb => params.generateurs[(seed % nb as u64) as usize].clone()
+    };
+
+    let structures = params.structures;
+
+    let base: Box<dyn WorldGenerator> = match nom.as_str() {
+        "overworld" => Box::new(Overworld::new(seed, params, taille_chunk)),
+        "grottes" => Box::new(Caves::new(seed, params, taille_chunk)),
+        "donjon" => Box::new(Dungeon::new(seed, taille_chunk)),
+        "archipel" => Box::new(Archipelago::new(seed, params, taille_chunk)),
+        _ => return Err(format!("generateur de monde inconnu '{}'", nom))
+    };
+
+    if structures > 0 {
+        Ok(Box::new(Structures::new(base, Prefab::load_all(PREFABS_PATH)?, structures, seed)))
+    } else {
+        Ok(base)
+    }
+}
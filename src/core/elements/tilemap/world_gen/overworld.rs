@@ -0,0 +1,31 @@
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::world_gen::params::WorldGenParams;
+use crate::core::elements::tilemap::world_gen::WorldGenerator;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+// monde ouvert : biomes par bruit
+pub struct Overworld {
+    seed: u64,
+    params: WorldGenParams,
+    taille_chunk: Vecteur2D<u32>
+}
+
+impl Overworld {
+    pub fn new(seed: u64, params: WorldGenParams, taille_chunk: Vecteur2D<u32>) -> Self {
+        Self { seed, params, taille_chunk }
+    }
+}
+
+impl WorldGenerator for Overworld {
+    fn nom(&self) -> &str {
+        "overworld"
+    }
+
+    fn taille_chunk(&self) -> Vecteur2D<u32> {
+        self.taille_chunk.clone()
+    }
+
+    fn tile_at(&self, x: i32, y: i32) -> TileType {
+        self.params.biome_at(self.seed, x, y)
+    }
+}
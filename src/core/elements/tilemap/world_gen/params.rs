@@ -0,0 +1,130 @@
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::elements::tilemap::noise::{bruit_fractal, bruit_valeur};
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::sdd::graine::melanger_graine;
+
+pub const WORLDGEN_PATH: &str = "assets/data/worldgen.ini";
+
+// parametres de generation des biomes, reglables par niveau (cf worldgen.ini)
+#[derive(Clone)]
+pub struct WorldGenParams {
+    pub generateurs: Vec<String>, // generateurs possibles pour ce niveau, choisis selon la graine du monde
+    pub structures: u32, // tentatives de pose de batiment par chunk (cf prefabs.ini)
+    pub echelle: f32,
+    pub octaves: u32,
+    pub persistance: f32,
+    pub lacunarite: f32,
+    pub biais_temperature: f32,
+    pub biais_humidite: f32,
+    pub seuil_froid: f32,
+    pub seuil_chaud: f32,
+    pub seuil_sec: f32,
+    pub seuil_foret: f32,
+    pub seuil_marais: f32,
+    pub bordure: f32
+}
+
+impl Default for WorldGenParams {
+    fn default() -> Self {
+        Self {
+            generateurs: vec!["overworld".to_string()],
+            structures: 2,
+            echelle: 0.02,
+            octaves: 4,
+            persistance: 0.5,
+            lacunarite: 2.0,
+            biais_temperature: 0.05,
+            biais_humidite: 0.0,
+            seuil_froid: 0.35,
+            seuil_chaud: 0.6,
+            seuil_sec: 0.4,
+            seuil_foret: 0.55,
+            seuil_marais: 0.68,
+            bordure: 0.04
+        }
+    }
+}
+
+impl WorldGenParams {
+    fn from_section(section: &IniSection) -> Self {
+        let defaut = Self::default();
+
+        let generateurs = section.get_list("generateurs");
+
+        Self {
+            generateurs: if generateurs.is_empty() { defaut.generateurs } else { generateurs },
+            structures: section.get_or("structures", defaut.structures),
+            echelle: section.get_or("echelle", defaut.echelle),
+            octaves: section.get_or("octaves", defaut.octaves),
+            persistance: section.get_or("persistance", defaut.persistance),
+            lacunarite: section.get_or("lacunarite", defaut.lacunarite),
+            biais_temperature: section.get_or("biais_temperature", defaut.biais_temperature),
+            biais_humidite: section.get_or("biais_humidite", defaut.biais_humidite),
+            seuil_froid: section.get_or("seuil_froid", defaut.seuil_froid),
+            seuil_chaud: section.get_or("seuil_chaud", defaut.seuil_chaud),
+            seuil_sec: section.get_or("seuil_sec", defaut.seuil_sec),
+            seuil_foret: section.get_or("seuil_foret", defaut.seuil_foret),
+            seuil_marais: section.get_or("seuil_marais", defaut.seuil_marais),
+            bordure: section.get_or("bordure", defaut.bordure)
+        }
+    }
+
+    // prend la section [niveau.N] avec le plus grand N <= niveau
+    pub fn load(path: &str, niveau: u32) -> Result<Self, String> {
+        let fichier = IniFile::load(path)?;
+
+        Ok(
+            fichier.sections_prefixees("niveau.")
+                .into_iter()
+                .filter_map(|section| {
+                    section.nom["niveau.".len()..].parse::<u32>().ok().map(|n| (n, section))
+                })
+                .filter(|(n, _)| *n <= niveau)
+                .max_by_key(|(n, _)| *n)
+                .map(|(_, section)| Self::from_section(section))
+                .unwrap_or_default()
+        )
+    }
+
+    pub fn temperature(&self, seed: u64, x: i32, y: i32) -> f32 {
+        self.carte(melanger_graine(seed, 1, 0), x, y) + self.biais_temperature
+    }
+
+    pub fn humidite(&self, seed: u64, x: i32, y: i32) -> f32 {
+        self.carte(melanger_graine(seed, 2, 0), x, y) + self.biais_humidite
+    }
+
+    fn carte(&self, seed: u64, x: i32, y: i32) -> f32 {
+        let valeur = bruit_fractal(
+            seed,
+            x as f32 * self.echelle,
+            y as f32 * self.echelle,
+            self.octaves,
+            self.persistance,
+            self.lacunarite
+        );
+
+        // bruit haute frequence : frontieres irregulieres entre biomes voisins
+        let bordure = (bruit_valeur(melanger_graine(seed, 3, 0), x as f32 * 0.5, y as f32 * 0.5) - 0.5) * 2.0 * self.bordure;
+
+        valeur + bordure
+    }
+
+    // biome d'une tuile (index global) selon la temperature et l'humidite
+    pub fn biome_at(&self, seed: u64, x: i32, y: i32) -> TileType {
+        let temperature = self.temperature(seed, x, y);
+        let humidite = self.humidite(seed, x, y);
+
+        if temperature < self.seuil_froid {
+            TileType::Snow
+        } else if humidite < self.seuil_sec {
+            if temperature > self.seuil_chaud { TileType::Sand } else { TileType::Herbe }
+        } else if humidite > self.seuil_marais {
+            TileType::Goo
+        } else if humidite > self.seuil_foret {
+            TileType::Wood
+        } else {
+            TileType::Herbe
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::core::data::ini::IniFile;
+use crate::core::elements::tilemap::tile::TileType;
+
+pub const PREFABS_PATH: &str = "assets/data/prefabs.ini";
+
+// batiment defini ligne par ligne dans prefabs.ini, None = terrain conserve
+pub struct Prefab {
+    pub nom: String,
+    pub poids: u32,
+    pub tuiles: Vec<Vec<Option<TileType>>>
+}
+
+impl Prefab {
+    pub fn load_all(path: &str) -> Result<Vec<Self>, String> {
+        IniFile::load(path)?
+            .sections_prefixees("prefab.")
+            .into_iter()
+            .map(|section| {
+                let nom = section.nom["prefab.".len()..].to_string();
+
+                let tuiles = section.get_all("ligne")
+                    .into_iter()
+                    .map(|ligne| {
+                        ligne.chars()
+                            .map(|symbole| Self::tuile_from_symbole(symbole)
+                                .ok_or(format!("{} : symbole inconnu '{}' dans le prefab '{}'", path, symbole, nom)))
+                            .collect::<Result<Vec<_>, String>>()
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                let largeur = tuiles.first().map(|ligne| ligne.len()).unwrap_or(0);
+                if largeur == 0 || tuiles.iter().any(|ligne| ligne.len() != largeur) {
+                    return Err(format!("{} : le prefab '{}' doit etre un rectangle non vide", path, nom));
+                }
+
+                Ok(Self { poids: section.get_or("poids", 1), nom, tuiles })
+            })
+            .collect::<Result<Vec<_>, String>>()
+    }
+
+    fn tuile_from_symbole(symbole: char) -> Option<Option<TileType>> {
+        match symbole {
+            '#' => Some(Some(TileType::Mur)),
+            '+' => Some(Some(TileType::Porte)),
+            '.' => Some(Some(TileType::Wood)),
+            '-' => Some(None),
+            _ => None
+        }
+    }
+
+    pub fn largeur(&self) -> i32 {
+        self.tuiles[0].len() as i32
+    }
+
+    pub fn hauteur(&self) -> i32 {
+        self.tuiles.len() as i32
+    }
+}
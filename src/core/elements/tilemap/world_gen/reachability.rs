@@ -0,0 +1,254 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use rand::RngCore;
+
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::world_gen::WorldGenerator;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+const MARGE: i32 = 20; // tuiles autour du depart et de la sortie ou le chemin peut faire un detour
+const NB_ESSAIS_SORTIE: u32 = 5;
+
+// tuiles generees, gardees par chunk le temps de la verification
+struct CarteTemporaire<'a> {
+    generateur: &'a dyn WorldGenerator,
+    chunks: HashMap<(i32, i32), Vec<Vec<TileType>>>,
+    bloquantes: Vec<TileType>
+}
+
+impl<'a> CarteTemporaire<'a> {
+    fn new(generateur: &'a dyn WorldGenerator) -> Self {
+        Self {
+            generateur,
+            chunks: HashMap::new(),
+            bloquantes: generateur.tuiles_bloquantes()
+        }
+    }
+
+    fn est_franchissable(&mut self, x: i32, y: i32) -> bool {
+        let taille = self.generateur.taille_chunk();
+        let (w, h) = (taille.x as i32, taille.y as i32);
+        let index = (x.div_euclid(w), y.div_euclid(h));
+        let generateur = self.generateur;
+
+        let chunk = self.chunks
+            .entry(index)
+            .or_insert_with(|| generateur.generate_chunk(&Vecteur2D::new(index.0, index.1)));
+
+        !self.bloquantes.contains(&chunk[y.rem_euclid(h) as usize][x.rem_euclid(w) as usize])
+    }
+
+    // le joueur (rayon 16) centre sur une tuile deborde sur la suivante : il occupe un carre de 2x2 tuiles
+    fn bloc_libre(&mut self, x: i32, y: i32) -> bool {
+        self.est_franchissable(x, y)
+            && self.est_franchissable(x + 1, y)
+            && self.est_franchissable(x, y + 1)
+            && self.est_franchissable(x + 1, y + 1)
+    }
+}
+
+// parcours en largeur depuis le depart, limite au rectangle englobant depart et arrivee (plus une marge)
+pub fn est_accessible(generateur: &dyn WorldGenerator, depart: &Vecteur2D<i32>, arrivee: &Vecteur2D<i32>) -> bool {
+    let mut carte = CarteTemporaire::new(generateur);
+
+    if !carte.bloc_libre(depart.x, depart.y) {
+        return false;
+    }
+
+    let min = Vecteur2D::new(depart.x.min(arrivee.x) - MARGE, depart.y.min(arrivee.y) - MARGE);
+    let max = Vecteur2D::new(depart.x.max(arrivee.x) + MARGE, depart.y.max(arrivee.y) + MARGE);
+
+    let mut visites = HashSet::from([(depart.x, depart.y)]);
+    let mut file = VecDeque::from([(depart.x, depart.y)]);
+
+    while let Some((x, y)) = file.pop_front() {
+        if (x, y) == (arrivee.x, arrivee.y) {
+            return true;
+        }
+
+        [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|(vx, vy)| *vx >= min.x && *vx <= max.x && *vy >= min.y && *vy <= max.y)
+            .for_each(|voisin| {
+                if !visites.contains(&voisin) && carte.bloc_libre(voisin.0, voisin.1) {
+                    visites.insert(voisin);
+                    file.push_back(voisin);
+                }
+            });
+    }
+
+    false
+}
+
+// tuiles bloquantes a remplacer pour ouvrir un couloir en L (2 tuiles de large) entre depart et arrivee
+pub fn chemin_a_creuser(generateur: &dyn WorldGenerator, depart: &Vecteur2D<i32>, arrivee: &Vecteur2D<i32>) -> Vec<(Vecteur2D<i32>, TileType)> {
+    let mut carte = CarteTemporaire::new(generateur);
+
+    let horizontal = (depart.x.min(arrivee.x)..=depart.x.max(arrivee.x) + 1)
+        .flat_map(|x| [(x, depart.y), (x, depart.y + 1)]);
+    let vertical = (depart.y.min(arrivee.y)..=depart.y.max(arrivee.y) + 1)
+        .flat_map(|y| [(arrivee.x, y), (arrivee.x + 1, y)]);
+
+    horizontal
+        .chain(vertical)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .filter(|(x, y)| !carte.est_franchissable(*x, *y))
+        .map(|(x, y)| (Vecteur2D::new(x, y), generateur.tuile_creusee(x, y)))
+        .collect::<Vec<_>>()
+}
+
+// place la sortie la ou le joueur peut l'atteindre : plusieurs tirages,
+// puis en dernier recours un couloir creuse jusqu'a elle
+pub fn placer_sortie(
+    generateur: &dyn WorldGenerator,
+    depart: &Vecteur2D<i32>,
+    zone_min: &Vecteur2D<i32>,
+    zone_max: &Vecteur2D<i32>,
+    rng: &mut dyn RngCore
+) -> (Vecteur2D<i32>, Vec<(Vecteur2D<i32>, TileType)>) {
+    let sorties = (0..NB_ESSAIS_SORTIE)
+        .map(|_| generateur.exit_location(zone_min, zone_max, rng))
+        .collect::<Vec<_>>();
+
+    match sorties.iter().find(|sortie| est_accessible(generateur, depart, sortie)) {
+        Some(sortie) => (sortie.clone(), vec![]),
+        None => (sorties[0].clone(), chemin_a_creuser(generateur, depart, &sorties[0]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::core::elements::tilemap::world_gen::choisir_generateur;
+    use crate::core::elements::tilemap::world_gen::params::WorldGenParams;
+
+    const NB_GRAINES: u64 = 200;
+
+    // generateur avec les tuiles creusees par placer_sortie, comme la tilemap du jeu
+    struct Creuse<'a> {
+        base: &'a dyn WorldGenerator,
+        modifications: HashMap<(i32, i32), TileType>
+    }
+
+    impl<'a> WorldGenerator for Creuse<'a> {
+        fn nom(&self) -> &str {
+            self.base.nom()
+        }
+
+        fn taille_chunk(&self) -> Vecteur2D<u32> {
+            self.base.taille_chunk()
+        }
+
+        fn tile_at(&self, x: i32, y: i32) -> TileType {
+            self.modifications.get(&(x, y)).cloned().unwrap_or_else(|| self.base.tile_at(x, y))
+        }
+
+        fn tuiles_bloquantes(&self) -> Vec<TileType> {
+            self.base.tuiles_bloquantes()
+        }
+    }
+
+    // murs partout sauf une poche autour du depart et de chaque coin de la zone : la sortie est toujours emmuree
+    struct Enclos {
+        poches: Vec<Vecteur2D<i32>>
+    }
+
+    impl WorldGenerator for Enclos {
+        fn nom(&self) -> &str {
+            "enclos"
+        }
+
+        fn taille_chunk(&self) -> Vecteur2D<u32> {
+            Vecteur2D::new(20, 20)
+        }
+
+        fn tile_at(&self, x: i32, y: i32) -> TileType {
+            if self.poches.iter().any(|poche| (x - poche.x).abs() <= 2 && (y - poche.y).abs() <= 2) {
+                TileType::Sand
+            } else {
+                TileType::Mur
+            }
+        }
+    }
+
+    fn sortie_accessible_apres_creusement(generateur: &dyn WorldGenerator, seed: u64, zone_min: &Vecteur2D<i32>, zone_max: &Vecteur2D<i32>) -> usize {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let depart = generateur.player_start();
+        let (sortie, chemin) = placer_sortie(generateur, &depart, zone_min, zone_max, &mut rng);
+        let nb_creusees = chemin.len();
+
+        let carte = Creuse {
+            base: generateur,
+            modifications: chemin.into_iter().map(|(index, r#type)| ((index.x, index.y), r#type)).collect()
+        };
+        assert!(
+            est_accessible(&carte, &depart, &sortie),
+            "{} (graine {}) : sortie {:?} inaccessible depuis {:?}",
+            generateur.nom(), seed, (sortie.x, sortie.y), (depart.x, depart.y)
+        );
+
+        nb_creusees
+    }
+
+    fn verifier_generateur(nom: &str) {
+        let zone_min = Vecteur2D::new(10, 10);
+        let zone_max = Vecteur2D::new(70, 70);
+
+        (0..NB_GRAINES).for_each(|seed| {
+            let params = WorldGenParams { generateurs: vec![nom.to_string()], ..WorldGenParams::default() };
+            let generateur = choisir_generateur(params, seed, Vecteur2D::new(20, 20))
+                .expect("erreur lors du choix du generateur de monde");
+
+            sortie_accessible_apres_creusement(&*generateur, seed, &zone_min, &zone_max);
+        });
+    }
+
+    #[test]
+    fn sortie_accessible_overworld() {
+        verifier_generateur("overworld");
+    }
+
+    #[test]
+    fn sortie_accessible_grottes() {
+        verifier_generateur("grottes");
+    }
+
+    #[test]
+    fn sortie_accessible_donjon() {
+        verifier_generateur("donjon");
+    }
+
+    #[test]
+    fn sortie_accessible_archipel() {
+        verifier_generateur("archipel");
+    }
+
+    #[test]
+    fn sortie_emmuree_creuse_un_couloir() {
+        let zone_min = Vecteur2D::new(10, 10);
+        let zone_max = Vecteur2D::new(50, 50);
+        let enclos = Enclos {
+            poches: vec![
+                Vecteur2D::new(2, 2),
+                zone_max.clone(),
+                Vecteur2D::new(zone_min.x, zone_max.y),
+                Vecteur2D::new(zone_max.x, zone_min.y)
+            ]
+        };
+
+        let depart = enclos.player_start();
+        let sortie = enclos.exit_location(&zone_min, &zone_max, &mut StdRng::seed_from_u64(0));
+        assert!(!est_accessible(&enclos, &depart, &sortie));
+
+        (0..20).for_each(|seed| {
+            assert!(sortie_accessible_apres_creusement(&enclos, seed, &zone_min, &zone_max) > 0);
+        });
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use crate::core::data::ini::IniFile;
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const SPAWNS_PATH: &str = "assets/data/spawns.ini";
+
+// regles d'apparition des ennemis (cf spawns.ini), distances en tuiles
+#[derive(Clone)]
+pub struct SpawnRules {
+    pub distance_min_joueur: f32,
+    pub espacement: f32,
+    pub essais: u32,
+    poids_biomes: HashMap<String, f32> // code du biome -> poids relatif, 1 si absent
+}
+
+impl SpawnRules {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let fichier = IniFile::load(path)?;
+
+        let (distance_min_joueur, espacement, essais) = match fichier.section("regles") {
+            Some(regles) => (
+                regles.get_or("distance_min_joueur", 15.0),
+                regles.get_or("espacement", 3.0),
+                regles.get_or("essais", 30)
+            ),
+            None => (15.0, 3.0, 30)
+        };
+
+        let poids_biomes = fichier.sections_prefixees("biome.")
+            .into_iter()
+            .map(|section| {
+                let code = section.nom["biome.".len()..].to_string();
+                TileType::from_code(code.as_str())
+                    .ok_or(format!("{} : biome inconnu '{}'", path, code))
+                    .map(|_| (code, section.get_or("poids", 1.0)))
+            })
+            .collect::<Result<HashMap<_, _>, String>>()?;
+
+        Ok(Self { distance_min_joueur, espacement, essais, poids_biomes })
+    }
+
+    pub fn poids(&self, r#type: &TileType) -> f32 {
+        *self.poids_biomes.get(r#type.code()).unwrap_or(&1.0)
+    }
+
+    pub fn poids_max(&self) -> f32 {
+        self.poids_biomes.values().fold(1.0, |max, poids| max.max(*poids))
+    }
+
+    // regles qui ne dependent que de la tuile (affichees par la vue de debug)
+    pub fn position_valide(&self, pos: &Vecteur2D<i32>, r#type: &TileType, bloquantes: &[TileType], depart: &Vecteur2D<i32>) -> bool {
+        !bloquantes.contains(r#type)
+            && self.poids(r#type) > 0.0
+            && distance_tuiles(pos, depart) >= self.distance_min_joueur
+    }
+
+    pub fn assez_espace(&self, pos: &Vecteur2D<i32>, autres: &[Vecteur2D<i32>]) -> bool {
+        autres.iter().all(|autre| distance_tuiles(pos, autre) >= self.espacement)
+    }
+}
+
+fn distance_tuiles(a: &Vecteur2D<i32>, b: &Vecteur2D<i32>) -> f32 {
+    Vecteur2D::new((b.x - a.x) as f32, (b.y - a.y) as f32).norme()
+}
@@ -0,0 +1,166 @@
+use std::cell::RefCell;
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::world_gen::prefab::Prefab;
+use crate::core::elements::tilemap::world_gen::WorldGenerator;
+use crate::core::sdd::cache::CacheBorne;
+use crate::core::sdd::graine::melanger_graine;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+// placements gardes pour les chunks autour du joueur ; ceux d'un chunk oublie sont recalcules a l'identique
+const NB_CHUNKS_EN_CACHE: usize = 256;
+
+// prefab pose dans un chunk, coin haut gauche en index global de tuile
+struct Placement {
+    x: i32,
+    y: i32,
+    prefab: usize
+}
+
+// pose des batiments par dessus un autre generateur : chaque chunk tire ses batiments
+// avec sa propre graine, sans chevauchement et sans toucher les bords du chunk
+// (un batiment n'est donc jamais coupe ni recouvert par celui d'un chunk voisin)
+pub struct Structures {
+    base: Box<dyn WorldGenerator>,
+    prefabs: Vec<Prefab>,
+    essais_par_chunk: u32,
+    seed: u64,
+    placements: RefCell<CacheBorne<(i32, i32), Vec<Placement>>>
+}
+
+impl Structures {
+    pub fn new(base: Box<dyn WorldGenerator>, prefabs: Vec<Prefab>, essais_par_chunk: u32, seed: u64) -> Self {
+        Self {
+            base,
+            prefabs,
+            essais_par_chunk,
+            seed: melanger_graine(seed, 6, 0),
+            placements: RefCell::new(CacheBorne::new(NB_CHUNKS_EN_CACHE))
+        }
+    }
+
+    fn tirer_prefab(&self, rng: &mut StdRng) -> Option<usize> {
+        let total = self.prefabs.iter().map(|prefab| prefab.poids).sum::<u32>();
+        if total == 0 {
+            return None;
+        }
+
+        let mut tirage = rng.gen_range(0..total);
+        self.prefabs.iter().position(|prefab| {
+            if tirage < prefab.poids {
+                true
+            } else {
+                tirage -= prefab.poids;
+                false
+            }
+        })
+    }
+
+    fn calculer_placements(&self, index: &Vecteur2D<i32>) -> Vec<Placement> {
+        let taille = self.base.taille_chunk();
+        let (largeur_chunk, hauteur_chunk) = (taille.x as i32, taille.y as i32);
+        let origine = Vecteur2D::new(index.x * largeur_chunk, index.y * hauteur_chunk);
+        let terrain = self.base.generate_chunk(index);
+        let bloquantes = self.base.tuiles_bloquantes();
+        let mut rng = StdRng::seed_from_u64(melanger_graine(self.seed, index.x, index.y));
+        let mut placements: Vec<Placement> = vec![];
+
+        (0..self.essais_par_chunk).for_each(|_| {
+            let prefab_index = match self.tirer_prefab(&mut rng) {
+                Some(prefab_index) => prefab_index,
+                None => return
+            };
+            let prefab = &self.prefabs[prefab_index];
+
+            if prefab.largeur() + 2 > largeur_chunk || prefab.hauteur() + 2 > hauteur_chunk {
+                return;
+            }
+
+            // coordonnees locales au chunk, une tuile de marge avec les bords
+            let x = rng.gen_range(1..=largeur_chunk - prefab.largeur() - 1);
+            let y = rng.gen_range(1..=hauteur_chunk - prefab.hauteur() - 1);
+
+            // une tuile d'ecart entre deux batiments pour garder les portes degagees
+            let chevauche = placements.iter().any(|autre| {
+                let autre_prefab = &self.prefabs[autre.prefab];
+                let (ax, ay) = (autre.x - origine.x, autre.y - origine.y);
+
+                x <= ax + autre_prefab.largeur() && ax <= x + prefab.largeur()
+                    && y <= ay + autre_prefab.hauteur() && ay <= y + prefab.hauteur()
+            });
+
+            let terrain_libre = (y..y + prefab.hauteur()).all(|ligne| {
+                (x..x + prefab.largeur()).all(|colonne| !bloquantes.contains(&terrain[ligne as usize][colonne as usize]))
+            });
+
+            if !chevauche && terrain_libre {
+                placements.push(Placement { x: origine.x + x, y: origine.y + y, prefab: prefab_index });
+            }
+        });
+
+        placements
+    }
+
+    fn tuile_de_structure(&self, x: i32, y: i32) -> Option<TileType> {
+        let taille = self.base.taille_chunk();
+        let index = (x.div_euclid(taille.x as i32), y.div_euclid(taille.y as i32));
+
+        let mut cache = self.placements.borrow_mut();
+        let placements = cache.get_or_insert_with(index, || self.calculer_placements(&Vecteur2D::new(index.0, index.1)));
+
+        placements.iter().find_map(|placement| {
+            let prefab = &self.prefabs[placement.prefab];
+            let (colonne, ligne) = (x - placement.x, y - placement.y);
+
+            if colonne >= 0 && ligne >= 0 && colonne < prefab.largeur() && ligne < prefab.hauteur() {
+                prefab.tuiles[ligne as usize][colonne as usize].clone()
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl WorldGenerator for Structures {
+    fn nom(&self) -> &str {
+        self.base.nom()
+    }
+
+    fn taille_chunk(&self) -> Vecteur2D<u32> {
+        self.base.taille_chunk()
+    }
+
+    fn tile_at(&self, x: i32, y: i32) -> TileType {
+        self.tuile_de_structure(x, y).unwrap_or_else(|| self.base.tile_at(x, y))
+    }
+
+    fn tuiles_bloquantes(&self) -> Vec<TileType> {
+        self.base.tuiles_bloquantes()
+    }
+
+    fn tuile_creusee(&self, x: i32, y: i32) -> TileType {
+        self.base.tuile_creusee(x, y)
+    }
+
+    fn generate_chunk(&self, index: &Vecteur2D<i32>) -> Vec<Vec<TileType>> {
+        let taille = self.base.taille_chunk();
+        let origine = Vecteur2D::new(index.x * taille.x as i32, index.y * taille.y as i32);
+
+        self.base.generate_chunk(index)
+            .into_iter()
+            .enumerate()
+            .map(|(ligne, tuiles)| {
+                tuiles.into_iter()
+                    .enumerate()
+                    .map(|(colonne, tuile)| {
+                        self.tuile_de_structure(origine.x + colonne as i32, origine.y + ligne as i32)
+                            .unwrap_or(tuile)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    }
+}
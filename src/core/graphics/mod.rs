@@ -22,4 +22,12 @@ pub trait CanDrawSprite {
         from_size: Option<Vecteur2D<u32>>,
         to_size: Option<Vecteur2D<u32>>
     ) -> Result<(), String>;
+
+    // rectangle plein, l'alpha de la couleur est respecte
+    fn draw_rect(
+        &mut self,
+        position: Vecteur2D<i32>,
+        size: Vecteur2D<u32>,
+        color: Color
+    ) -> Result<(), String>;
 }
\ No newline at end of file
@@ -11,4 +11,10 @@ impl Color {
             r, g, b, a: 0xff
         }
     }
+
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self {
+            r, g, b, a
+        }
+    }
 }
\ No newline at end of file
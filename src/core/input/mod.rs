@@ -1,5 +1,11 @@
 use crate::core::sdd::vecteur2d::Vecteur2D;
 
+#[derive(Clone, PartialEq, Debug)]
+pub enum TextInputEvent {
+    Text(String),
+    Backspace
+}
+
 pub trait CanManageInput {
     fn is_key_pressed(&self, value: &str) -> bool;
     fn is_key_mouse_pressed(&self, value: &str) -> bool;
@@ -16,4 +22,16 @@ pub trait CanManageInput {
     fn update_mouse_position(&mut self, position: Vecteur2D<f32>);
 
     fn get_mouse_position(&self) -> Vecteur2D<f32>;
+
+    // saisie de texte : evenements de la frame courante uniquement, ignores tant que la saisie n'est pas demandee
+    fn set_text_input_active(&mut self, active: bool);
+    fn is_text_input_active(&self) -> bool;
+    fn push_text_input(&mut self, event: TextInputEvent);
+    fn text_input(&self) -> Vec<TextInputEvent>;
+    fn clear_text_input(&mut self);
+
+    // molette : crans cumules pendant la frame courante (positif vers le haut)
+    fn push_mouse_wheel(&mut self, crans: i32);
+    fn mouse_wheel(&self) -> i32;
+    fn clear_mouse_wheel(&mut self);
 }
\ No newline at end of file
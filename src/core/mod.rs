@@ -4,4 +4,9 @@ pub mod graphics;
 pub mod musics;
 pub mod sdd;
 pub mod physics;
-pub mod elements;
\ No newline at end of file
+pub mod elements;
+pub mod data;
+pub mod settings;
+pub mod translations;
+pub mod scores;
+pub mod pathfinding;
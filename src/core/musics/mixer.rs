@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::musics::Bus;
+
+pub const SOUNDS_PATH: &str = "assets/data/sounds.ini";
+
+#[derive(Clone)]
+pub struct BusVolumes {
+    volumes: HashMap<Bus, f32>,
+    muets: HashMap<Bus, bool>
+}
+
+impl Default for BusVolumes {
+    fn default() -> Self {
+        Self {
+            volumes: Bus::ALL.into_iter().map(|bus| (bus, 1.0)).collect::<HashMap<_, _>>(),
+            muets: Bus::ALL.into_iter().map(|bus| (bus, false)).collect::<HashMap<_, _>>()
+        }
+    }
+}
+
+impl BusVolumes {
+    pub fn from_section(section: &IniSection) -> Self {
+        let mut bus_volumes = Self::default();
+        Bus::ALL.into_iter().for_each(|bus| {
+            bus_volumes.set_volume(bus, section.get_or(format!("volume_{}", bus.code()).as_str(), 1.0));
+            bus_volumes.set_muted(bus, section.get_or(format!("muet_{}", bus.code()).as_str(), false));
+        });
+        bus_volumes
+    }
+
+    pub fn write_section(&self, section: &mut IniSection) {
+        Bus::ALL.into_iter().for_each(|bus| {
+            section.set(format!("volume_{}", bus.code()).as_str(), self.volume(bus));
+            section.set(format!("muet_{}", bus.code()).as_str(), self.is_muted(bus));
+        });
+    }
+
+    pub fn volume(&self, bus: Bus) -> f32 {
+        *self.volumes.get(&bus).unwrap_or(&1.0)
+    }
+
+    pub fn set_volume(&mut self, bus: Bus, volume: f32) {
+        self.volumes.insert(bus, volume.clamp(0.0, 1.0));
+    }
+
+    pub fn is_muted(&self, bus: Bus) -> bool {
+        *self.muets.get(&bus).unwrap_or(&false)
+    }
+
+    pub fn set_muted(&mut self, bus: Bus, muted: bool) {
+        self.muets.insert(bus, muted);
+    }
+
+    // volume reel d'un bus : le bus master s'applique a tous les autres
+    pub fn effective(&self, bus: Bus) -> f32 {
+        let gain = |b: Bus| if self.is_muted(b) { 0.0 } else { self.volume(b) };
+
+        match bus {
+            Bus::Master => gain(Bus::Master),
+            _ => gain(Bus::Master) * gain(bus)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SoundDef {
+    pub fichier: String,
+    pub bus: Bus,
+    pub priorite: u8,
+    pub cooldown: f32,
+    pub distance_max: f32
+}
+
+impl SoundDef {
+    pub fn from_section(section: &IniSection) -> Result<Self, String> {
+        Ok(
+            Self {
+                fichier: section.get("fichier")
+                    .ok_or(format!("son '{}' : fichier manquant", section.nom))?
+                    .to_string(),
+                bus: section.get("bus").and_then(Bus::from_code).unwrap_or(Bus::Sfx),
+                priorite: section.get_or("priorite", 0u8),
+                cooldown: section.get_or("cooldown", 0.0f32),
+                distance_max: section.get_or("distance_max", 800.0f32)
+            }
+        )
+    }
+
+    pub fn load_all(path: &str) -> Result<HashMap<String, SoundDef>, String> {
+        IniFile::load(path)?
+            .sections
+            .iter()
+            .filter(|section| !section.nom.is_empty())
+            .map(|section| SoundDef::from_section(section).map(|def| (section.nom.clone(), def)))
+            .collect::<Result<HashMap<_, _>, String>>()
+    }
+}
+
+#[derive(Clone)]
+pub struct Voix {
+    pub id: String,
+    pub bus: Bus,
+    pub volume: i32,
+    pub priorite: u8,
+    pub debut: f32
+}
+
+pub struct ChannelPool {
+    pub voix: Vec<Option<Voix>>
+}
+
+impl ChannelPool {
+    pub fn new(nb_canaux: usize) -> Self {
+        Self {
+            voix: vec![None; nb_canaux]
+        }
+    }
+
+    // renvoie le canal a utiliser : un canal libre sinon on vole la voix la moins prioritaire
+    // (la plus ancienne a priorite egale), None si toutes les voix sont plus prioritaires
+    pub fn allouer<F>(&mut self, voix: Voix, est_libre: F) -> Option<usize>
+        where F: Fn(usize) -> bool
+    {
+        let canal_libre = (0..self.voix.len())
+            .find(|canal| self.voix[*canal].is_none() || est_libre(*canal));
+
+        let canal = canal_libre.or_else(|| {
+            self.voix.iter()
+                .enumerate()
+                .filter_map(|(canal, occupant)| occupant.as_ref().map(|o| (canal, o)))
+                .filter(|(_, occupant)| occupant.priorite <= voix.priorite)
+                .min_by(|(_, a), (_, b)| {
+                    a.priorite.cmp(&b.priorite)
+                        .then(a.debut.partial_cmp(&b.debut).unwrap_or(std::cmp::Ordering::Equal))
+                })
+                .map(|(canal, _)| canal)
+        })?;
+
+        self.voix[canal] = Some(voix);
+        Some(canal)
+    }
+
+    pub fn voix_du_bus(&self, bus: Bus) -> Vec<(usize, &Voix)> {
+        self.voix.iter()
+            .enumerate()
+            .filter_map(|(canal, voix)| voix.as_ref().map(|v| (canal, v)))
+            .filter(|(_, voix)| bus == Bus::Master || voix.bus == bus)
+            .collect::<Vec<_>>()
+    }
+}
+
+pub struct Mixer {
+    pub bus: BusVolumes,
+    pub pool: ChannelPool,
+    pub definitions: HashMap<String, SoundDef>,
+    derniere_lecture: HashMap<String, f32>
+}
+
+impl Mixer {
+    pub fn new(bus: BusVolumes, nb_canaux: usize, definitions: HashMap<String, SoundDef>) -> Self {
+        Self {
+            bus,
+            pool: ChannelPool::new(nb_canaux),
+            definitions,
+            derniere_lecture: HashMap::new()
+        }
+    }
+
+    // anti-spam : un son ne peut pas etre rejoue avant la fin de son cooldown
+    pub fn cooldown_ok(&mut self, id: &str, maintenant: f32) -> bool {
+        let cooldown = self.definitions.get(id).map(|def| def.cooldown).unwrap_or(0.0);
+
+        match self.derniere_lecture.get(id) {
+            Some(derniere) if maintenant - derniere < cooldown => false,
+            _ => {
+                self.derniere_lecture.insert(id.to_string(), maintenant);
+                true
+            }
+        }
+    }
+
+    pub fn volume_effectif(&self, bus: Bus, volume: i32) -> i32 {
+        (volume as f32 * self.bus.effective(bus)).round() as i32
+    }
+}
@@ -1,5 +1,57 @@
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub mod mixer;
+pub mod playlist;
+pub mod spatial;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Bus {
+    Master,
+    Music,
+    Sfx,
+    Ui
+}
+
+impl Bus {
+    pub const ALL: [Bus; 4] = [Bus::Master, Bus::Music, Bus::Sfx, Bus::Ui];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Bus::Master => "master",
+            Bus::Music => "music",
+            Bus::Sfx => "sfx",
+            Bus::Ui => "ui"
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Bus::ALL.into_iter().find(|bus| bus.code() == code)
+    }
+}
+
 pub trait CanPlayMusic {
-    fn play(&self, id: &str, volume: i32) -> Result<(), String>;
-    fn play_sound(&self, id: &str, volume: i32) -> Result<(), String>;
-    fn stop(&self) -> Result<(), String>;
-}
\ No newline at end of file
+    fn play(&mut self, id: &str, volume: i32) -> Result<(), String>;
+    fn fade_in(&mut self, id: &str, volume: i32, duree_ms: i32) -> Result<(), String>;
+    fn fade_out(&mut self, duree_ms: i32) -> Result<(), String>;
+    // sdl_mixer ne joue qu'une musique a la fois : fondu sortant puis fondu entrant
+    fn crossfade(&mut self, id: &str, volume: i32, duree_ms: i32) -> Result<(), String>;
+    fn pause(&mut self);
+    fn resume(&mut self);
+    fn current_track(&self) -> Option<String>;
+
+    // nom : "menu", "monde.snow", ... un nom inconnu se replie sur son prefixe ("monde")
+    fn play_playlist(&mut self, nom: &str) -> Result<(), String>;
+    // intensite entre 0 et 1, pilote le volume des couches de la playlist en cours
+    fn set_intensity(&mut self, intensite: f32);
+    fn update(&mut self, dt: f32) -> Result<(), String>;
+
+    // position : position du son dans le monde, None pour un son non spatialise (ui, ...)
+    fn play_sound(&mut self, id: &str, volume: i32, position: Option<Vecteur2D<f32>>) -> Result<(), String>;
+    fn set_listener(&mut self, position: Vecteur2D<f32>);
+    fn stop(&mut self) -> Result<(), String>;
+
+    fn set_bus_volume(&mut self, bus: Bus, volume: f32);
+    fn get_bus_volume(&self, bus: Bus) -> f32;
+    fn set_bus_muted(&mut self, bus: Bus, muted: bool);
+    fn is_bus_muted(&self, bus: Bus) -> bool;
+}
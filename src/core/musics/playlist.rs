@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::core::data::ini::{IniFile, IniSection};
+
+pub const PLAYLISTS_PATH: &str = "assets/data/playlists.ini";
+
+// largeur (en intensite) de la montee en volume d'une couche au dela de son seuil
+const RAMPE_COUCHE: f32 = 0.25;
+
+#[derive(Clone)]
+pub struct Couche {
+    pub son: String,
+    pub seuil: f32
+}
+
+#[derive(Clone)]
+pub struct Playlist {
+    pub pistes: Vec<String>,
+    pub volume: i32,
+    pub fondu_ms: i32,
+    pub couches: Vec<Couche>
+}
+
+impl Couche {
+    // gain de la couche (0..1) pour une intensite donnee (0..1)
+    pub fn gain(&self, intensite: f32) -> f32 {
+        ((intensite - self.seuil) / RAMPE_COUCHE).clamp(0.0, 1.0)
+    }
+}
+
+impl Playlist {
+    pub fn from_section(section: &IniSection) -> Self {
+        let couches = section.get_list("couches")
+            .into_iter()
+            .zip(section.get_list("seuils").into_iter().chain(std::iter::repeat(String::new())))
+            .map(|(son, seuil)| Couche { son, seuil: seuil.parse::<f32>().unwrap_or(0.5) })
+            .collect::<Vec<_>>();
+
+        Self {
+            pistes: section.get_list("pistes"),
+            volume: section.get_or("volume", 20),
+            fondu_ms: section.get_or("fondu", 1000),
+            couches
+        }
+    }
+
+    pub fn load_all(path: &str) -> Result<HashMap<String, Playlist>, String> {
+        Ok(
+            IniFile::load(path)?
+                .sections
+                .iter()
+                .filter(|section| !section.nom.is_empty())
+                .map(|section| (section.nom.clone(), Playlist::from_section(section)))
+                .collect::<HashMap<_, _>>()
+        )
+    }
+
+    pub fn piste_suivante(&self, index: usize) -> usize {
+        if self.pistes.is_empty() { 0 } else { (index + 1) % self.pistes.len() }
+    }
+}
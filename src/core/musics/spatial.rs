@@ -0,0 +1,39 @@
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub struct Spatialisation {
+    pub distance: u8, // 0 = sur l'ecouteur, 255 = a la limite d'audibilite
+    pub gauche: u8,
+    pub droite: u8
+}
+
+// None si la source est trop loin pour etre entendue
+pub fn spatialiser(
+    ecouteur: &Vecteur2D<f32>,
+    source: &Vecteur2D<f32>,
+    distance_max: f32,
+    largeur_panoramique: f32
+) -> Option<Spatialisation> {
+    let vecteur = Vecteur2D::<f32>::from_points(ecouteur, source);
+    let distance = vecteur.norme();
+
+    if distance > distance_max {
+        return None;
+    }
+
+    let ratio_distance = if distance_max > 0.0 { distance / distance_max } else { 0.0 };
+
+    // -1 : tout a gauche, 1 : tout a droite
+    let pan = if largeur_panoramique > 0.0 {
+        (vecteur.x / largeur_panoramique).clamp(-1.0, 1.0)
+    } else {
+        0.0
+    };
+
+    Some(
+        Spatialisation {
+            distance: (ratio_distance * 255.0) as u8,
+            gauche: (255.0 * (1.0 - pan).min(1.0)) as u8,
+            droite: (255.0 * (1.0 + pan).min(1.0)) as u8
+        }
+    )
+}
@@ -0,0 +1,240 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::core::data::ini::IniFile;
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::TileMapHudge;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const PATHFINDING_PATH: &str = "assets/data/pathfinding.ini";
+
+// (depart, arrivee) -> (chemin, None si introuvable ; age en secondes)
+type CacheChemins = HashMap<((i32, i32), (i32, i32)), (Option<Vec<Vecteur2D<i32>>>, f32)>;
+
+const VOISINS: [(i32, i32); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+pub enum ResultatChemin {
+    Chemin(Vec<Vecteur2D<i32>>), // cases a suivre, depart exclu, arrivee incluse (ou case la plus proche si recherche trop longue)
+    Introuvable,
+    EnAttente // budget de la frame epuise : redemander a la frame suivante
+}
+
+// issue d'une recherche A*
+enum Recherche {
+    Trouve(Vec<Vecteur2D<i32>>),
+    Introuvable,
+    Interrompue(Vec<Vecteur2D<i32>>) // limite de noeuds atteinte : chemin vers la case exploree la plus proche de l'arrivee
+}
+
+// A* partage par tous les ennemis : un cache des chemins recents et un budget de noeuds
+// par frame pour que plusieurs recherches simultanees ne fassent pas de pic
+pub struct Pathfinder {
+    couts: HashMap<String, f32>, // code de tuile -> cout de passage, 1 si absent
+    noeuds_par_frame: i64,
+    noeuds_max_par_recherche: usize,
+    duree_cache: f32,
+    budget_restant: i64,
+    cache: CacheChemins
+}
+
+#[derive(PartialEq)]
+struct Noeud {
+    estimation: f32,
+    pos: (i32, i32)
+}
+
+impl Eq for Noeud {}
+
+// tas min sur l'estimation
+impl Ord for Noeud {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimation.partial_cmp(&self.estimation).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Noeud {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Pathfinder {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let fichier = IniFile::load(path)?;
+
+        let couts = match fichier.section("couts") {
+            Some(section) => section.entrees
+                .iter()
+                .map(|(code, valeur)| {
+                    TileType::from_code(code).ok_or(format!("{} : type de tuile inconnu '{}'", path, code))?;
+                    valeur.parse::<f32>()
+                        .map(|cout| (code.clone(), cout))
+                        .map_err(|_| format!("{} : cout invalide '{}' pour '{}'", path, valeur, code))
+                })
+                .collect::<Result<HashMap<_, _>, String>>()?,
+            None => HashMap::new()
+        };
+
+        let (noeuds_par_frame, noeuds_max_par_recherche, duree_cache) = match fichier.section("budget") {
+            Some(budget) => (
+                budget.get_or("noeuds_par_frame", 3000),
+                budget.get_or("noeuds_max_par_recherche", 2000),
+                budget.get_or("duree_cache", 0.5)
+            ),
+            None => (3000, 2000, 0.5)
+        };
+
+        Ok(
+            Self {
+                couts,
+                noeuds_par_frame,
+                noeuds_max_par_recherche,
+                duree_cache,
+                budget_restant: noeuds_par_frame,
+                cache: HashMap::new()
+            }
+        )
+    }
+
+    // a appeler une fois par frame, avant les demandes de chemin
+    pub fn debut_frame(&mut self, dt: f32) {
+        self.budget_restant = self.noeuds_par_frame;
+
+        let duree_cache = self.duree_cache;
+        self.cache.retain(|_, (_, age)| {
+            *age += dt;
+            *age < duree_cache
+        });
+    }
+
+    pub fn chemin(&mut self, tilemap: &TileMapHudge, depart: &Vecteur2D<i32>, arrivee: &Vecteur2D<i32>) -> ResultatChemin {
+        if (depart.x, depart.y) == (arrivee.x, arrivee.y) {
+            return ResultatChemin::Chemin(vec![]);
+        }
+
+        let cle = ((depart.x, depart.y), (arrivee.x, arrivee.y));
+
+        let chemin = match self.cache.get(&cle) {
+            Some((chemin, _)) => chemin.clone(),
+            None if self.budget_restant <= 0 => return ResultatChemin::EnAttente,
+            None => {
+                // la recherche s'arrete avec le budget de la frame, sans jamais le depasser
+                let limite_frame = (self.budget_restant as usize) < self.noeuds_max_par_recherche;
+                let limite = (self.budget_restant as usize).min(self.noeuds_max_par_recherche);
+                let (recherche, noeuds_explores) = self.a_etoile(tilemap, depart, arrivee, limite);
+                self.budget_restant -= noeuds_explores as i64;
+
+                let chemin = match recherche {
+                    Recherche::Trouve(chemin) => Some(chemin),
+                    Recherche::Introuvable => None,
+                    // budget de la frame epuise : la recherche reprendra a la frame suivante
+                    Recherche::Interrompue(_) if limite_frame => return ResultatChemin::EnAttente,
+                    // cible trop loin : on se rapproche, un nouveau chemin sera demande au bout de celui-ci
+                    Recherche::Interrompue(chemin) if !chemin.is_empty() => Some(chemin),
+                    Recherche::Interrompue(_) => None
+                };
+                self.cache.insert(cle, (chemin.clone(), 0.0));
+                chemin
+            }
+        };
+
+        match chemin {
+            Some(chemin) => ResultatChemin::Chemin(chemin),
+            None => ResultatChemin::Introuvable
+        }
+    }
+
+    // None si la tuile est bloquante ou dans un chunk non charge
+    fn cout(&self, tilemap: &TileMapHudge, bloquantes: &[TileType], x: i32, y: i32) -> Option<f32> {
+        tilemap.get_tile_at(&Vecteur2D::new(x, y))
+            .filter(|tile| !bloquantes.contains(&tile.r#type))
+            .map(|tile| *self.couts.get(tile.r#type.code()).unwrap_or(&1.0))
+    }
+
+    fn heuristique(&self, a: (i32, i32), b: (i32, i32)) -> f32 {
+        // distance octile, avec le plus petit cout pour rester admissible
+        let cout_min = self.couts.values().fold(1.0f32, |min, cout| min.min(*cout));
+        let dx = (a.0 - b.0).abs() as f32;
+        let dy = (a.1 - b.1).abs() as f32;
+
+        cout_min * (dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy))
+    }
+
+    // renvoie l'issue de la recherche et le nombre de noeuds explores (au plus `limite`)
+    fn a_etoile(&self, tilemap: &TileMapHudge, depart: &Vecteur2D<i32>, arrivee: &Vecteur2D<i32>, limite: usize) -> (Recherche, usize) {
+        let bloquantes = tilemap.generateur.tuiles_bloquantes();
+        let depart = (depart.x, depart.y);
+        let arrivee = (arrivee.x, arrivee.y);
+
+        let mut ouverts = BinaryHeap::from([Noeud { estimation: self.heuristique(depart, arrivee), pos: depart }]);
+        let mut couts_connus: HashMap<(i32, i32), f32> = HashMap::from([(depart, 0.0)]);
+        let mut precedents: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut fermes: HashSet<(i32, i32)> = HashSet::new();
+        let mut noeuds_explores = 0;
+        let mut plus_proche = (self.heuristique(depart, arrivee), depart);
+
+        while let Some(Noeud { pos, .. }) = ouverts.pop() {
+            if pos == arrivee {
+                return (Recherche::Trouve(Self::reconstruire(&precedents, depart, arrivee)), noeuds_explores);
+            }
+
+            // un noeud peut etre dans le tas plusieurs fois : seule sa premiere sortie (la moins chere) compte
+            if !fermes.insert(pos) {
+                continue;
+            }
+
+            if noeuds_explores >= limite {
+                return (Recherche::Interrompue(Self::reconstruire(&precedents, depart, plus_proche.1)), noeuds_explores);
+            }
+            noeuds_explores += 1;
+
+            let distance = self.heuristique(pos, arrivee);
+            if distance < plus_proche.0 {
+                plus_proche = (distance, pos);
+            }
+
+            let cout_actuel = couts_connus[&pos];
+
+            VOISINS.iter().for_each(|(dx, dy)| {
+                let voisin = (pos.0 + dx, pos.1 + dy);
+
+                // en diagonale, pas de coupe entre deux murs
+                let diagonale = *dx != 0 && *dy != 0;
+                if diagonale
+                    && (self.cout(tilemap, &bloquantes, pos.0 + dx, pos.1).is_none()
+                        || self.cout(tilemap, &bloquantes, pos.0, pos.1 + dy).is_none()) {
+                    return;
+                }
+
+                if fermes.contains(&voisin) {
+                    return;
+                }
+
+                if let Some(cout) = self.cout(tilemap, &bloquantes, voisin.0, voisin.1) {
+                    let nouveau_cout = cout_actuel + cout * if diagonale { std::f32::consts::SQRT_2 } else { 1.0 };
+
+                    if couts_connus.get(&voisin).map(|connu| nouveau_cout < *connu).unwrap_or(true) {
+                        couts_connus.insert(voisin, nouveau_cout);
+                        precedents.insert(voisin, pos);
+                        ouverts.push(Noeud { estimation: nouveau_cout + self.heuristique(voisin, arrivee), pos: voisin });
+                    }
+                }
+            });
+        }
+
+        (Recherche::Introuvable, noeuds_explores)
+    }
+
+    fn reconstruire(precedents: &HashMap<(i32, i32), (i32, i32)>, depart: (i32, i32), arrivee: (i32, i32)) -> Vec<Vecteur2D<i32>> {
+        let mut chemin = vec![];
+        let mut courant = arrivee;
+
+        while courant != depart {
+            chemin.push(Vecteur2D::new(courant.0, courant.1));
+            courant = precedents[&courant];
+        }
+
+        chemin.reverse();
+        chemin
+    }
+}
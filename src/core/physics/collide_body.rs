@@ -47,6 +47,20 @@ impl CollideBody {
             })
             .is_some()
     }
+
+    // deplacement axe par axe : un axe est annule si le corps toucherait une tuile bloquante (glisse le long des murs)
+    pub fn deplacer_si_libre(&mut self, deplacement: &Vecteur2D<f32>, tilemap: &TileMapHudge, bloquantes: &[TileType]) {
+        [Vecteur2D::new(deplacement.x, 0f32), Vecteur2D::new(0f32, deplacement.y)]
+            .into_iter()
+            .for_each(|pas| {
+                let mut corps = self.clone();
+                corps.position += pas;
+
+                if !corps.is_collide_with_tilemap_hudge(tilemap, bloquantes.to_vec()) {
+                    self.position = corps.position;
+                }
+            });
+    }
 }
 
 impl CanCollideWithTileMap for CollideBody {
@@ -1 +1,3 @@
-pub mod collide_body;
\ No newline at end of file
+pub mod collide_body;
+pub mod raycast;
+pub mod shapes;
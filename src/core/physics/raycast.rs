@@ -0,0 +1,258 @@
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::TileMapHudge;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub struct ImpactRayon {
+    pub tuile: Vecteur2D<i32>, // index global de la tuile touchee
+    pub r#type: Option<TileType>, // None : chunk non charge (bloque le rayon)
+    pub point: Vecteur2D<f32>, // entree du rayon dans la tuile
+    pub distance: f32
+}
+
+// parcours de grille (DDA) de `depart` vers `arrivee` : premiere tuile bloquante traversee,
+// None si le segment est degage. La tuile de depart compte (rayon tire depuis un mur = impact immediat)
+pub fn raycast(tilemap: &TileMapHudge, depart: &Vecteur2D<f32>, arrivee: &Vecteur2D<f32>, bloquantes: &[TileType]) -> Option<ImpactRayon> {
+    let tile_size = tilemap.tile_size as f32;
+    let direction = Vecteur2D::<f32>::from_points(depart, arrivee);
+    let longueur = direction.norme();
+
+    let mut case = tilemap.get_tile_index_from_position(depart);
+    let case_arrivee = tilemap.get_tile_index_from_position(arrivee);
+
+    // t : avancement sur le segment, de 0 (depart) a 1 (arrivee)
+    let (pas_x, mut t_max_x, t_delta_x) = axe_dda(depart.x, direction.x, case.x, tile_size);
+    let (pas_y, mut t_max_y, t_delta_y) = axe_dda(depart.y, direction.y, case.y, tile_size);
+    let mut t = 0.0;
+
+    loop {
+        let tuile = tilemap.get_tile_at(&case);
+        let bloque = tuile.map(|tile| bloquantes.contains(&tile.r#type)).unwrap_or(true);
+
+        if bloque {
+            return Some(
+                ImpactRayon {
+                    tuile: case,
+                    r#type: tuile.map(|tile| tile.r#type.clone()),
+                    point: Vecteur2D::new(depart.x + direction.x * t, depart.y + direction.y * t),
+                    distance: longueur * t
+                }
+            );
+        }
+
+        if (case.x, case.y) == (case_arrivee.x, case_arrivee.y) {
+            return None;
+        }
+
+        if t_max_x < t_max_y {
+            t = t_max_x;
+            t_max_x += t_delta_x;
+            case.x += pas_x;
+        } else {
+            t = t_max_y;
+            t_max_y += t_delta_y;
+            case.y += pas_y;
+        }
+
+        if t > 1.0 {
+            return None;
+        }
+    }
+}
+
+// (pas, t du premier bord de tuile croise, t entre deux bords) sur un axe
+fn axe_dda(origine: f32, direction: f32, case: i32, tile_size: f32) -> (i32, f32, f32) {
+    if direction > 0.0 {
+        (1, ((case + 1) as f32 * tile_size - origine) / direction, tile_size / direction)
+    } else if direction < 0.0 {
+        (-1, (case as f32 * tile_size - origine) / direction, -tile_size / direction)
+    } else {
+        (0, f32::INFINITY, f32::INFINITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::core::elements::tilemap::world_gen::WorldGenerator;
+
+    const TAILLE_TUILE: f32 = 32.0;
+    const NB_GRAINES: u64 = 25;
+    const RAYONS_PAR_GRAINE: usize = 40;
+    const PAS_ECHANTILLONNAGE: f32 = 0.1; // en pixels, pour la verification par echantillonnage
+    const EPSILON: f32 = 0.01;
+
+    // murs a des positions connues, sable ailleurs
+    struct Murs {
+        murs: HashSet<(i32, i32)>
+    }
+
+    impl WorldGenerator for Murs {
+        fn nom(&self) -> &str {
+            "murs"
+        }
+
+        fn taille_chunk(&self) -> Vecteur2D<u32> {
+            Vecteur2D::new(20, 20)
+        }
+
+        fn tile_at(&self, x: i32, y: i32) -> TileType {
+            if self.murs.contains(&(x, y)) { TileType::Mur } else { TileType::Sand }
+        }
+    }
+
+    // chunks -2..=1 charges : tuiles -40..39 sur chaque axe, bords de chunk en -20, 0 et 20
+    fn tilemap(murs: HashSet<(i32, i32)>) -> TileMapHudge {
+        let mut tilemap = TileMapHudge::new(TAILLE_TUILE as u32, 2, 0, Box::new(Murs { murs }));
+        (-2..=1).for_each(|y| (-2..=1).for_each(|x| tilemap.ensure_chunk(&Vecteur2D::new(x, y))));
+        tilemap
+    }
+
+    fn pixel(tuile: f32) -> f32 {
+        tuile * TAILLE_TUILE
+    }
+
+    // tuiles traversees par le segment, dans l'ordre, avec l'avancement ou on y entre
+    fn echantillonner(depart: &Vecteur2D<f32>, arrivee: &Vecteur2D<f32>) -> Vec<((i32, i32), f32)> {
+        let longueur = Vecteur2D::<f32>::from_points(depart, arrivee).norme();
+        let nb_pas = (longueur / PAS_ECHANTILLONNAGE).ceil().max(1.0) as usize;
+        let mut tuiles: Vec<((i32, i32), f32)> = vec![];
+
+        (0..=nb_pas).for_each(|pas| {
+            let t = pas as f32 / nb_pas as f32;
+            let tuile = (
+                ((depart.x + (arrivee.x - depart.x) * t) / TAILLE_TUILE).floor() as i32,
+                ((depart.y + (arrivee.y - depart.y) * t) / TAILLE_TUILE).floor() as i32
+            );
+            if tuiles.last().map(|(derniere, _)| *derniere != tuile).unwrap_or(true) {
+                tuiles.push((tuile, t * longueur));
+            }
+        });
+
+        tuiles
+    }
+
+    // compare le raycast a l'echantillonnage du segment
+    fn verifier_rayon(tilemap: &TileMapHudge, murs: &HashSet<(i32, i32)>, depart: &Vecteur2D<f32>, arrivee: &Vecteur2D<f32>) {
+        let bloquantes = [TileType::Mur];
+        let premier_mur = echantillonner(depart, arrivee)
+            .into_iter()
+            .find(|(tuile, _)| murs.contains(tuile));
+
+        match raycast(tilemap, depart, arrivee, &bloquantes) {
+            None => assert!(
+                premier_mur.is_none(),
+                "rayon {:?} -> {:?} : mur {:?} traverse mais segment annonce degage",
+                (depart.x, depart.y), (arrivee.x, arrivee.y), premier_mur
+            ),
+            Some(impact) => {
+                assert!(murs.contains(&(impact.tuile.x, impact.tuile.y)), "impact sur une tuile libre");
+                assert!(impact.r#type == Some(TileType::Mur));
+
+                // le point d'impact est sur le segment, au bord (ou dans) la tuile touchee
+                let attendu = Vecteur2D::<f32>::from_points(depart, arrivee).unitaire()
+                    .map(|direction| Vecteur2D::new(depart.x + direction.x * impact.distance, depart.y + direction.y * impact.distance))
+                    .unwrap_or(depart.clone());
+                assert!(Vecteur2D::<f32>::from_points(&attendu, &impact.point).norme() < EPSILON * TAILLE_TUILE);
+                assert!(impact.point.x >= pixel(impact.tuile.x as f32) - EPSILON && impact.point.x <= pixel(impact.tuile.x as f32 + 1.0) + EPSILON);
+                assert!(impact.point.y >= pixel(impact.tuile.y as f32) - EPSILON && impact.point.y <= pixel(impact.tuile.y as f32 + 1.0) + EPSILON);
+
+                // aucun mur avant l'impact le long du segment
+                if let Some((tuile, distance)) = premier_mur {
+                    assert!(
+                        distance >= impact.distance - PAS_ECHANTILLONNAGE * 2.0,
+                        "rayon {:?} -> {:?} : mur {:?} a {} avant l'impact {:?} a {}",
+                        (depart.x, depart.y), (arrivee.x, arrivee.y), tuile, distance, (impact.tuile.x, impact.tuile.y), impact.distance
+                    );
+                }
+            }
+        }
+    }
+
+    fn point_au_hasard(rng: &mut StdRng) -> Vecteur2D<f32> {
+        Vecteur2D::new(pixel(rng.gen_range(-38.0..38.0)), pixel(rng.gen_range(-38.0..38.0)))
+    }
+
+    fn murs_au_hasard(rng: &mut StdRng, nombre: usize) -> HashSet<(i32, i32)> {
+        (0..nombre).map(|_| (rng.gen_range(-40..40), rng.gen_range(-40..40))).collect()
+    }
+
+    #[test]
+    fn segment_degage_sur_les_bords_de_chunk() {
+        let tilemap = tilemap(HashSet::new());
+        let bloquantes = [TileType::Mur];
+
+        // traverse x = -20, 0 et 20, puis y = -20, 0 et 20, puis en diagonale dans les coordonnees negatives
+        [
+            ((-25.5, 3.2), (25.5, 3.7)),
+            ((3.2, -25.5), (3.9, 25.5)),
+            ((-35.3, -35.1), (-1.2, -2.4)),
+            ((19.9, -20.1), (20.1, -19.9)),
+            ((-20.5, 19.5), (-19.5, 20.5))
+        ]
+            .iter()
+            .for_each(|((ax, ay), (bx, by))| {
+                let a = Vecteur2D::new(pixel(*ax), pixel(*ay));
+                let b = Vecteur2D::new(pixel(*bx), pixel(*by));
+                assert!(raycast(&tilemap, &a, &b, &bloquantes).is_none());
+                assert!(raycast(&tilemap, &b, &a, &bloquantes).is_none());
+            });
+    }
+
+    #[test]
+    fn impact_sur_le_premier_mur_derriere_un_bord_de_chunk() {
+        let murs = HashSet::from([(20, 5), (22, 5), (-21, -21), (-23, -23)]);
+        let tilemap = tilemap(murs);
+        let bloquantes = [TileType::Mur];
+
+        let impact = raycast(&tilemap, &Vecteur2D::new(pixel(15.5), pixel(5.5)), &Vecteur2D::new(pixel(30.5), pixel(5.5)), &bloquantes)
+            .expect("le mur en (20, 5) doit arreter le rayon");
+        assert_eq!((impact.tuile.x, impact.tuile.y), (20, 5));
+        assert!((impact.point.x - pixel(20.0)).abs() < EPSILON);
+
+        let impact = raycast(&tilemap, &Vecteur2D::new(pixel(-15.5), pixel(-15.5)), &Vecteur2D::new(pixel(-30.5), pixel(-30.5)), &bloquantes)
+            .expect("le mur en (-21, -21) doit arreter le rayon");
+        assert_eq!((impact.tuile.x, impact.tuile.y), (-21, -21));
+    }
+
+    #[test]
+    fn rayons_au_hasard_conformes_a_l_echantillonnage() {
+        (0..NB_GRAINES).for_each(|seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let murs = murs_au_hasard(&mut rng, 60);
+            let tilemap = tilemap(murs.clone());
+
+            (0..RAYONS_PAR_GRAINE).for_each(|_| {
+                let depart = point_au_hasard(&mut rng);
+                let arrivee = point_au_hasard(&mut rng);
+                verifier_rayon(&tilemap, &murs, &depart, &arrivee);
+            });
+        });
+    }
+
+    #[test]
+    fn rayons_symetriques() {
+        let bloquantes = [TileType::Mur];
+
+        (0..NB_GRAINES).for_each(|seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let murs = murs_au_hasard(&mut rng, 60);
+            let tilemap = tilemap(murs);
+
+            (0..RAYONS_PAR_GRAINE).for_each(|_| {
+                let a = point_au_hasard(&mut rng);
+                let b = point_au_hasard(&mut rng);
+                assert_eq!(
+                    raycast(&tilemap, &a, &b, &bloquantes).is_some(),
+                    raycast(&tilemap, &b, &a, &bloquantes).is_some(),
+                    "rayon {:?} <-> {:?} bloque dans un seul sens",
+                    (a.x, a.y), (b.x, b.y)
+                );
+            });
+        });
+    }
+}
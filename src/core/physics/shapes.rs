@@ -0,0 +1,61 @@
+use std::f32::consts::TAU;
+
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+// secteur de disque : tous les points a moins de `rayon` du centre et a moins de `ouverture / 2`
+// de la direction (angles en radians)
+#[derive(Clone)]
+pub struct ArcDeCercle {
+    pub centre: Vecteur2D<f32>,
+    pub angle: f32,
+    pub ouverture: f32,
+    pub rayon: f32
+}
+
+impl ArcDeCercle {
+    // None si la direction est nulle
+    pub fn new(centre: Vecteur2D<f32>, direction: &Vecteur2D<f32>, ouverture: f32, rayon: f32) -> Option<Self> {
+        direction.unitaire().map(|unitaire| Self {
+            centre,
+            angle: unitaire.y.atan2(unitaire.x),
+            ouverture,
+            rayon
+        })
+    }
+
+    // vrai si le cercle (position, rayon) chevauche le secteur
+    // (approximation sur les bords : le cercle est vu depuis le centre de l'arc)
+    pub fn touche_cercle(&self, position: &Vecteur2D<f32>, rayon: f32) -> bool {
+        let vers = Vecteur2D::<f32>::from_points(&self.centre, position);
+        let distance = vers.norme();
+
+        if distance > self.rayon + rayon {
+            return false;
+        }
+        if distance <= rayon {
+            return true;
+        }
+
+        let tolerance = (rayon / distance).asin();
+        ecart_angle(vers.y.atan2(vers.x), self.angle) <= self.ouverture / 2.0 + tolerance
+    }
+
+    // points regulierement espaces sur le bord exterieur de l'arc
+    pub fn points_du_bord(&self, nombre: u32) -> Vec<Vecteur2D<f32>> {
+        let debut = self.angle - self.ouverture / 2.0;
+        let pas = if nombre > 1 { self.ouverture / (nombre - 1) as f32 } else { 0.0 };
+
+        (0..nombre)
+            .map(|i| {
+                let angle = debut + pas * i as f32;
+                Vecteur2D::new(self.centre.x + angle.cos() * self.rayon, self.centre.y + angle.sin() * self.rayon)
+            })
+            .collect()
+    }
+}
+
+// ecart absolu entre deux angles, dans [0, pi]
+fn ecart_angle(a: f32, b: f32) -> f32 {
+    let ecart = (a - b).rem_euclid(TAU);
+    ecart.min(TAU - ecart)
+}
@@ -3,13 +3,19 @@ use std::rc::Rc;
 use crate::core::graphics::{CanDrawSprite, CanDrawText};
 use crate::core::input::CanManageInput;
 use crate::core::musics::CanPlayMusic;
+use crate::core::scene::scene_amelioration::SceneAmelioration;
 use crate::core::scene::scene_game_over::SceneGameOver;
+use crate::core::scene::scene_inventaire::SceneInventaire;
 use crate::core::scene::scene_world::SceneWorld;
 use crate::core::scene::scene_menu::SceneMenu;
+use crate::core::scene::scene_world::scene_world_data::SceneWorldData;
+use crate::core::translations::TranslationService;
 
 pub mod scene_menu;
 pub mod scene_world;
 pub mod scene_game_over;
+pub mod scene_inventaire;
+pub mod scene_amelioration;
 
 pub enum SceneEnum<SpriteService, TextService, InputService, MusicService>
     where
@@ -19,8 +25,10 @@ pub enum SceneEnum<SpriteService, TextService, InputService, MusicService>
         MusicService: CanPlayMusic
 {
     SceneMenu(SceneMenu<SpriteService, TextService, InputService, MusicService>),
-    SceneWorld(SceneWorld<SpriteService, TextService, InputService, MusicService>),
+    SceneWorld(Box<SceneWorld<SpriteService, TextService, InputService, MusicService>>),
     SceneGameOver(SceneGameOver<SpriteService, TextService, InputService, MusicService>),
+    SceneInventaire(Box<SceneInventaire<SpriteService, TextService, InputService, MusicService>>),
+    SceneAmelioration(SceneAmelioration<SpriteService, TextService, InputService, MusicService>),
 }
 
 pub struct SceneManager<SpriteService, TextService, InputService, MusicService>
@@ -45,12 +53,14 @@ impl<SpriteService, TextService, InputService, MusicService> SceneManager<Sprite
         text_service: Rc<RefCell<TextService>>,
         sprite_service: Rc<RefCell<SpriteService>>,
         music_service: Rc<RefCell<MusicService>>,
+        translation_service: Rc<RefCell<TranslationService>>,
     ) -> Self {
         let scene_menu = SceneMenu::new(
             Rc::clone(&key_manager),
             Rc::clone(&text_service),
             Rc::clone(&sprite_service),
-            Rc::clone(&music_service)
+            Rc::clone(&music_service),
+            Rc::clone(&translation_service)
         );
         Self { current: SceneEnum::SceneMenu(scene_menu) }
     }
@@ -59,11 +69,31 @@ impl<SpriteService, TextService, InputService, MusicService> SceneManager<Sprite
         let nouvelle_scene = match &mut self.current {
             SceneEnum::SceneMenu(menu) => menu.on_scene(dt),
             SceneEnum::SceneWorld(world) => world.on_scene(dt),
-            SceneEnum::SceneGameOver(game_over) => game_over.on_scene(dt)
+            SceneEnum::SceneGameOver(game_over) => game_over.on_scene(dt),
+            SceneEnum::SceneInventaire(inventaire) => inventaire.on_scene(dt),
+            SceneEnum::SceneAmelioration(amelioration) => amelioration.on_scene(dt)
         };
 
         if let Some(x) = nouvelle_scene {
-            self.current = x;
+            let precedente = std::mem::replace(&mut self.current, x);
+
+            // l'inventaire s'ouvre par dessus le monde : il garde la scene du monde et la rend a la fermeture
+            if let (SceneEnum::SceneInventaire(inventaire), SceneEnum::SceneWorld(monde)) = (&mut self.current, precedente) {
+                inventaire.monde = Some(monde);
+            }
+        }
+    }
+
+    // a la fermeture du jeu, la partie en cours est sauvegardee
+    pub fn on_quit(&mut self) -> Result<(), String> {
+        match &self.current {
+            SceneEnum::SceneWorld(world) => world.data.save(),
+            SceneEnum::SceneInventaire(inventaire) => inventaire.monde.as_ref()
+                .map(|monde| monde.data.save())
+                .unwrap_or(Ok(())),
+            // le monde suivant est sauvegarde avec les ameliorations proposees, reproposees en continuant la partie
+            SceneEnum::SceneAmelioration(amelioration) => SceneWorldData::new(amelioration.data.run.clone()).save(),
+            _ => Ok(())
         }
     }
 }
@@ -0,0 +1,148 @@
+pub mod scene_amelioration_data;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::graphics::{CanDrawSprite, CanDrawText};
+use crate::core::graphics::models::color::Color;
+use crate::core::input::CanManageInput;
+use crate::core::musics::CanPlayMusic;
+use crate::core::scene::scene_amelioration::scene_amelioration_data::SceneAmeliorationData;
+use crate::core::scene::scene_world::run_state::RunState;
+use crate::core::scene::scene_world::SceneWorld;
+use crate::core::scene::scene_world::upgrade::EffetAmelioration;
+use crate::core::scene::SceneEnum;
+use crate::core::translations::TranslationService;
+
+// choix d'une amelioration entre deux mondes, avant de generer le monde suivant
+pub struct SceneAmelioration<SpriteService, TextService, InputService, MusicService>
+    where
+        SpriteService: CanDrawSprite,
+        TextService: CanDrawText,
+        InputService: CanManageInput,
+        MusicService: CanPlayMusic
+{
+    pub input_service: Rc<RefCell<InputService>>,
+    pub text_service: Rc<RefCell<TextService>>,
+    pub sprite_service: Rc<RefCell<SpriteService>>,
+    pub music_service: Rc<RefCell<MusicService>>,
+    pub translation_service: Rc<RefCell<TranslationService>>,
+    pub data: SceneAmeliorationData
+}
+
+impl<SpriteService, TextService, InputService, MusicService> SceneAmelioration<SpriteService, TextService, InputService, MusicService>
+    where
+        SpriteService: CanDrawSprite,
+        TextService: CanDrawText,
+        InputService: CanManageInput,
+        MusicService: CanPlayMusic
+{
+    pub fn on_scene(
+        &mut self,
+        _dt: f32
+    ) -> Option<SceneEnum<SpriteService, TextService, InputService, MusicService>> {
+
+        let next_scene = self.change_scene();
+
+        self.draw_choix().expect("erreur lors de l'affichage des ameliorations");
+
+        self.data.touches_tenues = self.input_service.borrow().key_pressed().into_iter().collect();
+        next_scene
+    }
+
+    pub fn new(
+        key_manager: Rc<RefCell<InputService>>,
+        text_service: Rc<RefCell<TextService>>,
+        sprite_service: Rc<RefCell<SpriteService>>,
+        music_service: Rc<RefCell<MusicService>>,
+        translation_service: Rc<RefCell<TranslationService>>,
+        run: RunState
+    ) -> Self {
+        let touches_tenues = key_manager.borrow().key_pressed().into_iter().collect();
+
+        Self {
+            input_service: key_manager,
+            text_service,
+            sprite_service,
+            music_service,
+            translation_service,
+            data: SceneAmeliorationData::new(run, touches_tenues)
+        }
+    }
+
+    // vrai seulement a la frame ou la touche est enfoncee
+    fn vient_d_appuyer(&self, touche: &str) -> bool {
+        self.input_service.borrow().is_key_pressed(touche) && !self.data.touches_tenues.contains(touche)
+    }
+
+    // touches 1 a 3, sur l'appui seulement : l'amelioration rejoint la partie et le monde suivant est genere
+    // (sans amelioration a proposer, on passe directement au monde suivant)
+    fn change_scene(&mut self) -> Option<SceneEnum<SpriteService, TextService, InputService, MusicService>> {
+        let choisie = (0..self.data.choix.len())
+            .find(|index| self.vient_d_appuyer((index + 1).to_string().as_str()));
+
+        if choisie.is_none() && !self.data.choix.is_empty() {
+            return None;
+        }
+
+        let mut run = self.data.run.clone();
+        run.choix_en_attente.clear();
+        if let Some(index) = choisie {
+            run.ameliorations.push(self.data.choix[index].nom.clone());
+        }
+
+        Some(
+            SceneEnum::SceneWorld(Box::new(
+                SceneWorld::new(
+                    Rc::clone(&self.input_service),
+                    Rc::clone(&self.text_service),
+                    Rc::clone(&self.sprite_service),
+                    Rc::clone(&self.music_service),
+                    Rc::clone(&self.translation_service),
+                    run
+                )
+            ))
+        )
+    }
+
+    fn draw_choix(&mut self) -> Result<(), String> {
+        let traductions = self.translation_service.borrow();
+        let text_service = self.text_service.borrow();
+
+        text_service.create_text(
+            traductions.tr_args("amelioration.titre", &[("n", self.data.run.compteur_de_monde.to_string())]).as_str(),
+            32 * 2, 32 * 2,
+            30,
+            Color::rgb(200, 150, 0)
+        )?;
+
+        self.data.choix
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, amelioration)| {
+                let effet = match amelioration.effet {
+                    EffetAmelioration::Vitesse(valeur) => traductions.tr_args("effet.vitesse", &[("n", valeur.to_string())]),
+                    EffetAmelioration::Ramassage(valeur) => traductions.tr_args("effet.ramassage", &[("n", valeur.to_string())]),
+                    EffetAmelioration::Degats(valeur) => traductions.tr_args("effet.degats", &[("n", (valeur * 100.0).round().to_string())]),
+                    EffetAmelioration::Discretion(valeur) => traductions.tr_args("effet.discretion", &[("n", (valeur * 100.0).round().to_string())])
+                };
+                let ligne = traductions.tr_args("amelioration.choix", &[
+                    ("touche", (index + 1).to_string()),
+                    ("nom", traductions.tr(format!("amelioration.{}", amelioration.nom).as_str())),
+                    ("rarete", traductions.tr(format!("rarete.{}", amelioration.rarete.nom).as_str()))
+                ]);
+                let (r, g, b) = amelioration.rarete.couleur;
+                let y = 32 * 5 + 64 * index as i32;
+
+                text_service.create_text(ligne.as_str(), 32 * 2, y, 22, Color::rgb(r, g, b))?;
+                text_service.create_text(effet.as_str(), 32 * 3, y + 26, 18, Color::rgb(200, 150, 100))
+            })?;
+
+        text_service.create_text(
+            traductions.tr("amelioration.aide").as_str(),
+            32 * 2, 600 - 32 * 2,
+            18,
+            Color::rgb(255, 0, 0)
+        )
+    }
+}
@@ -0,0 +1,35 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::core::scene::scene_world::run_state::RunState;
+use crate::core::scene::scene_world::upgrade::{Amelioration, Ameliorations, UPGRADES_PATH};
+
+pub const NB_CHOIX: usize = 3;
+
+pub struct SceneAmeliorationData {
+    pub run: RunState, // partie en route vers le monde suivant
+    pub choix: Vec<Rc<Amelioration>>,
+    pub touches_tenues: HashSet<String> // touches deja enfoncees a la frame precedente
+}
+
+impl SceneAmeliorationData {
+    // les touches tenues a l'arrivee (ex : le tir sur 1 a 9) ne choisissent rien
+    // un choix interrompu en quittant le jeu est repropose tel quel, sinon les ameliorations sont tirees
+    pub fn new(mut run: RunState, touches_tenues: HashSet<String>) -> Self {
+        let ameliorations = Ameliorations::load(UPGRADES_PATH)
+            .expect("erreur lors du chargement des ameliorations");
+
+        let choix = if run.choix_en_attente.is_empty() {
+            ameliorations.tirer_choix(NB_CHOIX, &mut rand::thread_rng())
+        } else {
+            run.choix_en_attente
+                .iter()
+                .filter_map(|nom| ameliorations.get(nom))
+                .collect::<Vec<_>>()
+        };
+        // garde pour la sauvegarde si le jeu est quitte avant le choix
+        run.choix_en_attente = choix.iter().map(|amelioration| amelioration.nom.clone()).collect();
+
+        Self { run, choix, touches_tenues }
+    }
+}
@@ -5,11 +5,14 @@ use std::rc::Rc;
 
 use crate::core::graphics::{CanDrawSprite, CanDrawText};
 use crate::core::graphics::models::color::Color;
-use crate::core::input::CanManageInput;
+use crate::core::input::{CanManageInput, TextInputEvent};
 use crate::core::musics::CanPlayMusic;
-use crate::core::scene::scene_game_over::scene_game_over_data::SceneGameOverData;
+use crate::core::scene::scene_game_over::scene_game_over_data::{SceneGameOverData, TAILLE_NOM_MAX};
 use crate::core::scene::SceneEnum;
 use crate::core::scene::scene_menu::SceneMenu;
+use crate::core::scene::scene_world::run_state::RunState;
+use crate::core::scores::{HighScore, HighScoreTable};
+use crate::core::translations::TranslationService;
 
 pub struct SceneGameOver<SpriteService, TextService, InputService, MusicService>
     where
@@ -22,6 +25,7 @@ pub struct SceneGameOver<SpriteService, TextService, InputService, MusicService>
     pub text_service: Rc<RefCell<TextService>>,
     pub sprite_service: Rc<RefCell<SpriteService>>,
     pub music_service: Rc<RefCell<MusicService>>,
+    pub translation_service: Rc<RefCell<TranslationService>>,
     pub data: SceneGameOverData
 }
 
@@ -39,13 +43,16 @@ impl<SpriteService, TextService, InputService, MusicService> SceneGameOver<Sprit
 
         self.init_scene().expect("erreur lors de l'initialisation du menu");
 
+        self.update_nom_joueur();
         let next_scene = self.change_scene();
 
         // self.draw_planetes().expect("erreur lors de l'affichage des planetes");
 
         self.draw_text_title();
+        self.draw_text_nom_joueur();
         self.draw_text_for_change_scene();
 
+        self.data.touches_tenues = self.input_service.borrow().key_pressed().into_iter().collect();
         next_scene
     }
 
@@ -54,37 +61,93 @@ impl<SpriteService, TextService, InputService, MusicService> SceneGameOver<Sprit
         text_service: Rc<RefCell<TextService>>,
         sprite_service: Rc<RefCell<SpriteService>>,
         music_service: Rc<RefCell<MusicService>>,
-        nb_monde_decouvert: u32,
+        translation_service: Rc<RefCell<TranslationService>>,
+        run: RunState
     ) -> Self {
+        let touches_tenues = key_manager.borrow().key_pressed().into_iter().collect();
+
         Self {
             input_service: key_manager,
             text_service,
             sprite_service,
             music_service,
-            data: SceneGameOverData {
-                is_init: false,
-                nb_monde_decouvert
-            }
+            translation_service,
+            data: SceneGameOverData::new(run, touches_tenues)
         }
     }
 
+    // vrai seulement a la frame ou la touche est enfoncee
+    fn vient_d_appuyer(&self, touche: &str) -> bool {
+        self.input_service.borrow().is_key_pressed(touche) && !self.data.touches_tenues.contains(touche)
+    }
+
     fn init_scene(&mut self) -> Result<(), String> {
         if !self.data.is_init {
             self.data.is_init = true;
-            self.music_service.borrow().play("digital-love", 1)
+            self.music_service.borrow_mut().play_playlist("game_over")
         } else {
             Ok(())
         }
     }
 
+    // la saisie ne commence qu'une fois relachees les touches tenues en arrivant (deplacements, armes),
+    // sinon la repetition des touches remplirait le nom
+    fn update_nom_joueur(&mut self) {
+        if !self.input_service.borrow().is_text_input_active() {
+            if self.input_service.borrow().key_pressed().is_empty() {
+                self.input_service.borrow_mut().set_text_input_active(true);
+            }
+            return;
+        }
+
+        let evenements = self.input_service.borrow().text_input();
+
+        evenements.into_iter().for_each(|evenement| match evenement {
+            TextInputEvent::Text(texte) => {
+                // la police ne contient que les caracteres ascii
+                texte.chars()
+                    .filter(|c| c.is_ascii_graphic() || *c == ' ')
+                    .for_each(|c| {
+                        if self.data.nom_joueur.len() < TAILLE_NOM_MAX {
+                            self.data.nom_joueur.push(c);
+                        }
+                    });
+            }
+            TextInputEvent::Backspace => {
+                self.data.nom_joueur.pop();
+            }
+        });
+    }
+
+    fn enregistrer_score(&self) -> Result<(), String> {
+        let nom = match self.data.nom_joueur.trim() {
+            "" => "???",
+            nom => nom
+        };
+
+        let mut table = HighScoreTable::load()?;
+        table.ajouter(HighScore::new(nom, &self.data.run));
+        table.save()
+    }
+
     fn change_scene(&mut self) -> Option<SceneEnum<SpriteService, TextService, InputService, MusicService>> {
-        if self.input_service.borrow().is_key_pressed("Escape") {
-            self.music_service.borrow().stop().expect("erreur lors de l'arret de la musique");
+        let enregistre = self.vient_d_appuyer("Return");
+
+        // un tableau illisible n'est pas ecrase : le score est perdu mais le jeu continue
+        if enregistre {
+            if let Err(erreur) = self.enregistrer_score() {
+                eprintln!("erreur lors de l'enregistrement du score : {}", erreur);
+            }
+        }
+
+        if enregistre || self.vient_d_appuyer("Escape") {
+            self.input_service.borrow_mut().set_text_input_active(false);
             let scene_menu = SceneMenu::new(
                 Rc::clone(&self.input_service),
                 Rc::clone(&self.text_service),
                 Rc::clone(&self.sprite_service),
-                Rc::clone(&self.music_service)
+                Rc::clone(&self.music_service),
+                Rc::clone(&self.translation_service)
             );
             Some(SceneEnum::SceneMenu(scene_menu))
         } else {
@@ -95,7 +158,7 @@ impl<SpriteService, TextService, InputService, MusicService> SceneGameOver<Sprit
     fn draw_text_title(&mut self) {
         self.text_service.borrow_mut()
             .create_text(
-                format!("Game Over").as_str(),
+                self.translation_service.borrow().tr("game_over.titre").as_str(),
                 32 * 1,
                 0 + 32 * 2,
                 40u32,
@@ -104,22 +167,57 @@ impl<SpriteService, TextService, InputService, MusicService> SceneGameOver<Sprit
 
         self.text_service.borrow_mut()
             .create_text(
-                format!("explored {} worlds", self.data.nb_monde_decouvert).as_str(),
+                self.translation_service.borrow()
+                    .tr_pluriel("game_over.mondes_explores", self.data.run.compteur_de_monde, &[])
+                    .as_str(),
                 32 * 2,
                 0 + 32 * 4,
                 30u32,
                 Color::rgb(100u8, 0u8, 0u8)
             ).expect("erreur lors de l'affichage");
+
+        self.text_service.borrow_mut()
+            .create_text(
+                self.translation_service.borrow()
+                    .tr_args("game_over.score", &[("n", self.data.run.score.to_string())])
+                    .as_str(),
+                32 * 2,
+                0 + 32 * 5 + 8,
+                24u32,
+                Color::rgb(100u8, 0u8, 0u8)
+            ).expect("erreur lors de l'affichage");
     }
 
     fn draw_text_for_change_scene(&mut self) {
         self.text_service.borrow_mut()
             .create_text(
-                "[press Escape]",
+                self.translation_service.borrow().tr("game_over.appuyer_echap").as_str(),
                 32 * 6,
                 600 - 32 * 3,
                 32u32,
                 Color::rgb(255u8, 0u8, 0u8)
             ).expect("erreur lors de l'affichage");
     }
+
+    fn draw_text_nom_joueur(&mut self) {
+        let traductions = self.translation_service.borrow();
+
+        self.text_service.borrow_mut()
+            .create_text(
+                traductions.tr_args("game_over.nom", &[("nom", format!("{}_", self.data.nom_joueur))]).as_str(),
+                32 * 2,
+                32 * 7,
+                24u32,
+                Color::rgb(200u8, 150u8, 0u8)
+            ).expect("erreur lors de l'affichage");
+
+        self.text_service.borrow_mut()
+            .create_text(
+                traductions.tr("game_over.enregistrer").as_str(),
+                32 * 2,
+                32 * 9,
+                20u32,
+                Color::rgb(200u8, 150u8, 100u8)
+            ).expect("erreur lors de l'affichage");
+    }
 }
\ No newline at end of file
@@ -1,13 +1,24 @@
+use std::collections::HashSet;
+
+use crate::core::scene::scene_world::run_state::RunState;
+
+pub const TAILLE_NOM_MAX: usize = 12;
+
 pub struct SceneGameOverData {
     pub is_init: bool,
-    pub nb_monde_decouvert: u32
+    pub run: RunState,
+    pub nom_joueur: String,
+    pub touches_tenues: HashSet<String> // touches deja enfoncees a la frame precedente
 }
 
 impl SceneGameOverData {
-    pub fn new(nb_monde_decouvert: u32) -> Self {
+    // les touches tenues a la mort du joueur ne valident pas le nom
+    pub fn new(run: RunState, touches_tenues: HashSet<String>) -> Self {
         Self {
             is_init: false,
-            nb_monde_decouvert
+            run,
+            nom_joueur: String::new(),
+            touches_tenues
         }
     }
-}
\ No newline at end of file
+}
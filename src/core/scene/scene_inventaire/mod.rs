@@ -0,0 +1,200 @@
+pub mod scene_inventaire_data;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::graphics::{CanDrawSprite, CanDrawText};
+use crate::core::graphics::models::color::Color;
+use crate::core::input::CanManageInput;
+use crate::core::musics::CanPlayMusic;
+use crate::core::scene::scene_inventaire::scene_inventaire_data::SceneInventaireData;
+use crate::core::scene::scene_world::SceneWorld;
+use crate::core::scene::SceneEnum;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+use crate::core::translations::TranslationService;
+
+// inventaire affiche par dessus le monde, qui est mis en pause
+// (le SceneManager confie la scene du monde a l'inventaire a l'ouverture)
+pub struct SceneInventaire<SpriteService, TextService, InputService, MusicService>
+    where
+        SpriteService: CanDrawSprite,
+        TextService: CanDrawText,
+        InputService: CanManageInput,
+        MusicService: CanPlayMusic
+{
+    pub input_service: Rc<RefCell<InputService>>,
+    pub text_service: Rc<RefCell<TextService>>,
+    pub sprite_service: Rc<RefCell<SpriteService>>,
+    pub music_service: Rc<RefCell<MusicService>>,
+    pub translation_service: Rc<RefCell<TranslationService>>,
+    pub monde: Option<Box<SceneWorld<SpriteService, TextService, InputService, MusicService>>>,
+    pub data: SceneInventaireData
+}
+
+impl<SpriteService, TextService, InputService, MusicService> SceneInventaire<SpriteService, TextService, InputService, MusicService>
+    where
+        SpriteService: CanDrawSprite,
+        TextService: CanDrawText,
+        InputService: CanManageInput,
+        MusicService: CanPlayMusic
+{
+    pub fn on_scene(
+        &mut self,
+        _dt: f32
+    ) -> Option<SceneEnum<SpriteService, TextService, InputService, MusicService>> {
+
+        if let Some(monde) = self.monde.as_mut() {
+            monde.draw_monde();
+        }
+
+        self.update_selection();
+        self.update_actions();
+        let next_scene = self.change_scene();
+
+        self.draw_fond().expect("erreur lors de l'affichage du fond de l'inventaire");
+        self.draw_inventaire().expect("erreur lors de l'affichage de l'inventaire");
+
+        self.data.touches_tenues = self.input_service.borrow().key_pressed().into_iter().collect();
+        next_scene
+    }
+
+    pub fn new(
+        key_manager: Rc<RefCell<InputService>>,
+        text_service: Rc<RefCell<TextService>>,
+        sprite_service: Rc<RefCell<SpriteService>>,
+        music_service: Rc<RefCell<MusicService>>,
+        translation_service: Rc<RefCell<TranslationService>>
+    ) -> Self {
+        Self {
+            input_service: key_manager,
+            text_service,
+            sprite_service,
+            music_service,
+            translation_service,
+            monde: None,
+            data: SceneInventaireData::default()
+        }
+    }
+
+    // vrai seulement a la frame ou la touche est enfoncee
+    fn vient_d_appuyer(&self, touche: &str) -> bool {
+        self.input_service.borrow().is_key_pressed(touche) && !self.data.touches_tenues.contains(touche)
+    }
+
+    fn nb_lignes(&self) -> usize {
+        self.monde.as_ref()
+            .map(|monde| {
+                let inventaire = &monde.data.player.inventaire;
+                inventaire.piles.len() + inventaire.equipes.len()
+            })
+            .unwrap_or(0)
+    }
+
+    fn update_selection(&mut self) {
+        let nb_lignes = self.nb_lignes();
+        if nb_lignes == 0 {
+            self.data.selection = 0;
+            return;
+        }
+
+        if self.vient_d_appuyer("Z") || self.vient_d_appuyer("Up") {
+            self.data.selection = (self.data.selection + nb_lignes - 1) % nb_lignes;
+        }
+        if self.vient_d_appuyer("S") || self.vient_d_appuyer("Down") {
+            self.data.selection = (self.data.selection + 1) % nb_lignes;
+        }
+        self.data.selection = self.data.selection.min(nb_lignes - 1);
+    }
+
+    // Entree : utilise ou equipe l'item selectionne, ou retire l'equipement selectionne
+    fn update_actions(&mut self) {
+        if !self.vient_d_appuyer("Return") {
+            return;
+        }
+
+        let selection = self.data.selection;
+        if let Some(monde) = self.monde.as_mut() {
+            let data = &mut monde.data;
+            let nb_piles = data.player.inventaire.piles.len();
+
+            if selection < nb_piles {
+                data.player.utiliser_item(selection, &mut data.run.monnaie);
+            } else {
+                data.player.desequiper(selection - nb_piles);
+            }
+        }
+    }
+
+    fn change_scene(&mut self) -> Option<SceneEnum<SpriteService, TextService, InputService, MusicService>> {
+        if self.vient_d_appuyer("I") || self.vient_d_appuyer("Escape") {
+            self.monde.take().map(|mut monde| {
+                // la touche de fermeture ne doit ni rouvrir l'inventaire ni quitter le monde
+                monde.data.touche_inventaire_pressee = true;
+                monde.data.touche_echap_pressee = true;
+                SceneEnum::SceneWorld(monde)
+            })
+        } else {
+            None
+        }
+    }
+
+    fn draw_fond(&mut self) -> Result<(), String> {
+        self.sprite_service.borrow_mut().draw_rect(
+            Vecteur2D::new(0, 0),
+            Vecteur2D::new(800, 600), // fixme utiliser un service window afin de recup les infos de la window
+            Color::rgba(0, 0, 0, 170)
+        )
+    }
+
+    fn draw_inventaire(&mut self) -> Result<(), String> {
+        let monde = match self.monde.as_ref() {
+            Some(monde) => monde,
+            None => return Ok(())
+        };
+        let inventaire = &monde.data.player.inventaire;
+        let traductions = self.translation_service.borrow();
+        let text_service = self.text_service.borrow();
+
+        text_service.create_text(
+            traductions.tr_args(
+                "inventaire.titre",
+                &[("n", inventaire.piles.len().to_string()), ("max", inventaire.capacite.to_string())]
+            ).as_str(),
+            32 * 2, 32,
+            30,
+            Color::rgb(200, 150, 0)
+        )?;
+
+        let lignes_piles = inventaire.piles.iter().map(|pile| {
+            traductions.tr_args(
+                "inventaire.pile",
+                &[("item", traductions.tr(format!("item.{}", pile.definition.nom).as_str())), ("n", pile.quantite.to_string())]
+            )
+        });
+        let lignes_equipes = inventaire.equipes.iter().map(|definition| {
+            traductions.tr_args("inventaire.equipe", &[("item", traductions.tr(format!("item.{}", definition.nom).as_str()))])
+        });
+
+        lignes_piles.chain(lignes_equipes)
+            .enumerate()
+            .try_for_each(|(index, ligne)| {
+                let couleur = if index == self.data.selection {
+                    Color::rgb(255, 255, 0)
+                } else {
+                    Color::rgb(200, 150, 100)
+                };
+                text_service.create_text(ligne.as_str(), 32 * 2, 32 * 3 + 22 * index as i32, 20, couleur)
+            })?;
+
+        if inventaire.piles.is_empty() && inventaire.equipes.is_empty() {
+            text_service.create_text(traductions.tr("inventaire.vide").as_str(), 32 * 2, 32 * 3, 20, Color::rgb(200, 150, 100))?;
+        }
+
+        text_service.create_text(
+            traductions.tr("inventaire.aide").as_str(),
+            32 * 2, 600 - 32 * 2,
+            18,
+            Color::rgb(255, 0, 0)
+        )
+    }
+}
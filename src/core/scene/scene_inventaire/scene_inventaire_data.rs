@@ -0,0 +1,16 @@
+use std::collections::HashSet;
+
+pub struct SceneInventaireData {
+    pub selection: usize, // cases de l'inventaire puis equipements portes
+    pub touches_tenues: HashSet<String> // touches deja enfoncees a la frame precedente
+}
+
+// la touche qui a ouvert l'inventaire est consideree comme deja tenue
+impl Default for SceneInventaireData {
+    fn default() -> Self {
+        Self {
+            selection: 0,
+            touches_tenues: ["I".to_string()].into_iter().collect()
+        }
+    }
+}
@@ -4,11 +4,17 @@ use std::rc::Rc;
 use crate::core::graphics::{CanDrawSprite, CanDrawText};
 use crate::core::graphics::models::color::Color;
 use crate::core::input::CanManageInput;
-use crate::core::musics::CanPlayMusic;
+use crate::core::musics::{Bus, CanPlayMusic};
 use crate::core::scene::SceneEnum;
+use crate::core::scene::scene_amelioration::SceneAmelioration;
 use crate::core::scene::scene_world::SceneWorld;
+use crate::core::scene::scene_world::scene_world_data::SceneWorldData;
 use crate::core::scene::scene_menu::scene_menu_data::SceneMenuData;
 use crate::core::sdd::vecteur2d::Vecteur2D;
+use crate::core::scene::scene_world::run_state::RunState;
+use crate::core::scores::HighScoreTable;
+use crate::core::settings::Settings;
+use crate::core::translations::TranslationService;
 
 pub mod scene_menu_data;
 
@@ -23,6 +29,7 @@ pub struct SceneMenu<SpriteService, TextService, InputService, MusicService>
     pub text_service: Rc<RefCell<TextService>>,
     pub sprite_service: Rc<RefCell<SpriteService>>,
     pub music_service: Rc<RefCell<MusicService>>,
+    pub translation_service: Rc<RefCell<TranslationService>>,
     pub data: SceneMenuData
 }
 
@@ -42,15 +49,24 @@ impl<SpriteService, TextService, InputService, MusicService> SceneMenu<SpriteSer
 
         let next_scene = self.change_scene();
         self.update_panel();
+        self.update_langue();
+        self.update_muet();
+        self.update_scores();
 
 
         self.draw_planetes().expect("erreur lors de l'affichage des planetes");
 
         if self.data.panel_draw {
             self.draw_panel().expect("erreur lors de l'affichage du panel");
+        } else if self.data.affiche_scores {
+            self.draw_scores().expect("erreur lors de l'affichage des scores");
         } else {
             self.draw_text_title();
             self.draw_text_for_change_scene();
+            self.draw_text_continuer();
+            self.draw_text_langue();
+            self.draw_text_muet();
+            self.draw_text_scores();
         }
 
         next_scene
@@ -61,15 +77,26 @@ impl<SpriteService, TextService, InputService, MusicService> SceneMenu<SpriteSer
         text_service: Rc<RefCell<TextService>>,
         sprite_service: Rc<RefCell<SpriteService>>,
         music_service: Rc<RefCell<MusicService>>,
+        translation_service: Rc<RefCell<TranslationService>>,
     ) -> Self {
         Self {
             input_service: key_manager,
             text_service,
             sprite_service,
             music_service,
+            translation_service,
             data: SceneMenuData {
                 is_init: false,
-                panel_draw: true
+                panel_draw: true,
+                touche_langue_pressee: false,
+                touche_muet_pressee: false,
+                touche_scores_pressee: false,
+                affiche_scores: false,
+                sauvegarde_existe: SceneWorldData::save_exists(),
+                highscores: HighScoreTable::load().unwrap_or_else(|erreur| {
+                    eprintln!("erreur lors du chargement des scores : {}", erreur);
+                    HighScoreTable { scores: vec![] }
+                })
             }
         }
     }
@@ -77,23 +104,59 @@ impl<SpriteService, TextService, InputService, MusicService> SceneMenu<SpriteSer
     fn init_scene(&mut self) -> Result<(), String> {
         if !self.data.is_init {
             self.data.is_init = true;
-            self.music_service.borrow().play("digital-love", 1)
+            self.music_service.borrow_mut().play_playlist("menu")
         } else {
             Ok(())
         }
     }
 
     fn change_scene(&mut self) -> Option<SceneEnum<SpriteService, TextService, InputService, MusicService>> {
-        if self.input_service.borrow().is_key_pressed("Space") && !self.data.panel_draw {
-            self.music_service.borrow().stop().expect("erreur lors de l'arret de la musique");
+        if self.input_service.borrow().is_key_pressed("C") && !self.data.panel_draw && self.data.sauvegarde_existe {
+            // sauvegarde illisible, trop recente ou qui cite un element retire des fichiers de donnees :
+            // elle est supprimee et le menu reste affiche, sans "continuer"
+            let data = match SceneWorldData::load() {
+                Ok(data) => data,
+                Err(erreur) => {
+                    eprintln!("erreur lors du chargement de la sauvegarde : {}", erreur);
+                    if let Err(erreur) = SceneWorldData::delete_save() {
+                        eprintln!("{}", erreur);
+                    }
+                    self.data.sauvegarde_existe = false;
+                    return None;
+                }
+            };
+            // jeu quitte pendant le choix d'une amelioration : le choix reprend avant le monde
+            if !data.run.choix_en_attente.is_empty() {
+                return Some(SceneEnum::SceneAmelioration(
+                    SceneAmelioration::new(
+                        Rc::clone(&self.input_service),
+                        Rc::clone(&self.text_service),
+                        Rc::clone(&self.sprite_service),
+                        Rc::clone(&self.music_service),
+                        Rc::clone(&self.translation_service),
+                        data.etat_de_la_partie()
+                    )
+                ));
+            }
+            let scene_chargee = SceneWorld::from_data(
+                Rc::clone(&self.input_service),
+                Rc::clone(&self.text_service),
+                Rc::clone(&self.sprite_service),
+                Rc::clone(&self.music_service),
+                Rc::clone(&self.translation_service),
+                data
+            );
+            Some(SceneEnum::SceneWorld(Box::new(scene_chargee)))
+        } else if self.input_service.borrow().is_key_pressed("Space") && !self.data.panel_draw {
             let scene_exemple = SceneWorld::new(
                 Rc::clone(&self.input_service),
                 Rc::clone(&self.text_service),
                 Rc::clone(&self.sprite_service),
                 Rc::clone(&self.music_service),
-                1
+                Rc::clone(&self.translation_service),
+                RunState::nouvelle_partie()
             );
-            Some(SceneEnum::SceneWorld(scene_exemple))
+            Some(SceneEnum::SceneWorld(Box::new(scene_exemple)))
         } else {
             None
         }
@@ -105,6 +168,52 @@ impl<SpriteService, TextService, InputService, MusicService> SceneMenu<SpriteSer
         }
     }
 
+    fn update_langue(&mut self) {
+        let est_pressee = self.input_service.borrow().is_key_pressed("L");
+
+        if est_pressee && !self.data.touche_langue_pressee {
+            let nouvelle_langue = self.translation_service.borrow().langue.suivante();
+            self.translation_service.borrow_mut().set_langue(nouvelle_langue);
+
+            let mut settings = Settings::load();
+            settings.langue = nouvelle_langue;
+            // la langue reste changee pour cette session meme si le fichier ne peut pas etre ecrit
+            if let Err(erreur) = settings.save() {
+                eprintln!("erreur lors de la sauvegarde des reglages : {}", erreur);
+            }
+        }
+
+        self.data.touche_langue_pressee = est_pressee;
+    }
+
+    fn update_scores(&mut self) {
+        let est_pressee = self.input_service.borrow().is_key_pressed("H");
+
+        if est_pressee && !self.data.touche_scores_pressee && !self.data.panel_draw {
+            self.data.affiche_scores = !self.data.affiche_scores;
+        }
+
+        self.data.touche_scores_pressee = est_pressee;
+    }
+
+    fn update_muet(&mut self) {
+        let est_pressee = self.input_service.borrow().is_key_pressed("M");
+
+        if est_pressee && !self.data.touche_muet_pressee {
+            let est_muet = !self.music_service.borrow().is_bus_muted(Bus::Master);
+            self.music_service.borrow_mut().set_bus_muted(Bus::Master, est_muet);
+
+            let mut settings = Settings::load();
+            settings.audio.set_muted(Bus::Master, est_muet);
+            // le son reste coupe pour cette session meme si le fichier ne peut pas etre ecrit
+            if let Err(erreur) = settings.save() {
+                eprintln!("erreur lors de la sauvegarde des reglages : {}", erreur);
+            }
+        }
+
+        self.data.touche_muet_pressee = est_pressee;
+    }
+
     fn draw_panel(&mut self) -> Result<(), String> {
         self.sprite_service.borrow_mut().draw_sprite(
             "panel",
@@ -116,7 +225,7 @@ impl<SpriteService, TextService, InputService, MusicService> SceneMenu<SpriteSer
         self.text_service
             .borrow()
             .create_text(
-                "Work in progress",
+                self.translation_service.borrow().tr("menu.wip").as_str(),
                 32 * 5, 64,
                 30,
                 Color::rgb(200, 150, 0)
@@ -125,43 +234,7 @@ impl<SpriteService, TextService, InputService, MusicService> SceneMenu<SpriteSer
         self.text_service
             .borrow()
             .create_text(
-                "in coming :",
-                32 * 3, 32 * 4,
-                25,
-                Color::rgb(200, 150, 0)
-            )?;
-
-        self.text_service
-            .borrow()
-            .create_text(
-                "-> enemie generator",
-                32 * 3, 32 * 5,
-                25,
-                Color::rgb(200, 150, 100)
-            )?;
-
-        self.text_service
-            .borrow()
-            .create_text(
-                "-> weapons",
-                32 * 3, 32 * 6,
-                25,
-                Color::rgb(200, 150, 100)
-            )?;
-
-        self.text_service
-            .borrow()
-            .create_text(
-                "-> random buildings",
-                32 * 3, 32 * 7,
-                25,
-                Color::rgb(200, 150, 100)
-            )?;
-
-        self.text_service
-            .borrow()
-            .create_text(
-                "[Press Escape]",
+                self.translation_service.borrow().tr("menu.appuyer_echap").as_str(),
                 32 * 6, 32 * 15,
                 30,
                 Color::rgb(255, 0, 0)
@@ -213,11 +286,119 @@ impl<SpriteService, TextService, InputService, MusicService> SceneMenu<SpriteSer
     fn draw_text_for_change_scene(&mut self) {
         self.text_service.borrow_mut()
             .create_text(
-                "[press space]",
+                self.translation_service.borrow().tr("menu.appuyer_espace").as_str(),
                 32 * 6,
                 600 - 32 * 3,
                 32u32,
                 Color::rgb(255u8, 0u8, 0u8)
             ).expect("erreur lors de l'affichage");
     }
+
+    fn draw_text_langue(&mut self) {
+        let traductions = self.translation_service.borrow();
+        let langue = traductions.tr(format!("langue.{}", traductions.langue.code()).as_str());
+
+        self.text_service.borrow_mut()
+            .create_text(
+                traductions.tr_args("menu.langue", &[("langue", langue)]).as_str(),
+                32,
+                600 - 32,
+                16u32,
+                Color::rgb(200u8, 150u8, 100u8)
+            ).expect("erreur lors de l'affichage");
+    }
+
+    fn draw_text_muet(&mut self) {
+        let id_etat = if self.music_service.borrow().is_bus_muted(Bus::Master) { "menu.son_coupe" } else { "menu.son_actif" };
+        let traductions = self.translation_service.borrow();
+
+        self.text_service.borrow_mut()
+            .create_text(
+                traductions.tr_args("menu.son", &[("etat", traductions.tr(id_etat))]).as_str(),
+                32 * 14,
+                600 - 32,
+                16u32,
+                Color::rgb(200u8, 150u8, 100u8)
+            ).expect("erreur lors de l'affichage");
+    }
+
+    fn draw_text_continuer(&mut self) {
+        if self.data.sauvegarde_existe {
+            self.text_service.borrow_mut()
+                .create_text(
+                    self.translation_service.borrow().tr("menu.continuer").as_str(),
+                    32 * 6,
+                    600 - 32 * 5,
+                    24u32,
+                    Color::rgb(200u8, 150u8, 0u8)
+                ).expect("erreur lors de l'affichage");
+        }
+    }
+
+    fn draw_text_scores(&mut self) {
+        self.text_service.borrow_mut()
+            .create_text(
+                self.translation_service.borrow().tr("menu.scores").as_str(),
+                32 * 6,
+                600 - 32 * 4,
+                20u32,
+                Color::rgb(200u8, 150u8, 100u8)
+            ).expect("erreur lors de l'affichage");
+    }
+
+    fn draw_scores(&mut self) -> Result<(), String> {
+        self.sprite_service.borrow_mut().draw_sprite(
+            "panel",
+            Vecteur2D::new(32, 32),
+            Some(Vecteur2D::new(100, 100)),
+            Some(Vecteur2D::new(800 - 32 * 2, 600 - 32 * 2))
+        )?;
+
+        let traductions = self.translation_service.borrow();
+
+        self.text_service.borrow().create_text(
+            traductions.tr("scores.titre").as_str(),
+            32 * 2, 64,
+            30,
+            Color::rgb(200, 150, 0)
+        )?;
+
+        if self.data.highscores.scores.is_empty() {
+            self.text_service.borrow().create_text(
+                traductions.tr("scores.vide").as_str(),
+                32 * 2, 32 * 4,
+                16,
+                Color::rgb(200, 150, 100)
+            )?;
+        }
+
+        self.data.highscores.scores
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, score)| {
+                let ligne = traductions.tr_args("scores.ligne", &[
+                    ("rang", (index + 1).to_string()),
+                    ("nom", score.nom.clone()),
+                    ("mondes", score.mondes.to_string()),
+                    ("score", score.score.to_string()),
+                    ("temps", score.temps_formate()),
+                    ("tues", score.ennemis_tues.to_string()),
+                    ("date", score.date.clone())
+                ]);
+
+                self.text_service.borrow().create_text(
+                    ligne.as_str(),
+                    32 * 2, 32 * 4 + 24 * index as i32,
+                    14,
+                    Color::rgb(200, 150, 100)
+                )
+            })?;
+
+        self.text_service.borrow().create_text(
+            traductions.tr("menu.scores").as_str(),
+            32 * 6, 32 * 16,
+            20,
+            Color::rgb(255, 0, 0)
+        )
+    }
 }
\ No newline at end of file
@@ -1,4 +1,12 @@
+use crate::core::scores::HighScoreTable;
+
 pub struct SceneMenuData {
     pub is_init: bool,
-    pub panel_draw: bool
+    pub panel_draw: bool,
+    pub touche_langue_pressee: bool,
+    pub touche_muet_pressee: bool,
+    pub touche_scores_pressee: bool,
+    pub affiche_scores: bool,
+    pub sauvegarde_existe: bool,
+    pub highscores: HighScoreTable
 }
\ No newline at end of file
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use rand::Rng;
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::scene::scene_world::enemy_ai::ProfilIa;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const ENEMIES_PATH: &str = "assets/data/enemies.ini";
+
+// comportement propre a un archetype, en plus de la machine a etats commune
+#[derive(Clone)]
+pub enum Capacite {
+    Aucune,
+    Tireur { portee: f32, cadence: f32, vitesse_projectile: f32, duree_projectile: f32, son: String }, // s'arrete a portee et tire toutes les `cadence` secondes
+    Fouisseur { distance_surgissement: f32 } // enfoui dans le goo, surgit quand le joueur approche
+}
+
+// type d'ennemi defini dans enemies.ini
+pub struct Archetype {
+    pub nom: String,
+    pub sprite: String,
+    pub taille_sprite: Vecteur2D<u32>, // taille de l'image source
+    pub taille_affichage: u32,
+    pub rayon: f32, // rayon de collision en pixels
+    pub points_de_vie: f32,
+    pub degats: f32, // au contact et par projectile
+    pub recul: f32, // vitesse de recul donnee au joueur touche
+    pub profil: ProfilIa,
+    pub capacite: Capacite,
+    pub biomes: Vec<TileType>,
+    pub butin: String, // table de loot.ini tiree a la mort
+    pub points: u32, // score gagne en le tuant
+    pub poids: f32
+}
+
+impl Archetype {
+    fn from_section(path: &str, section: &IniSection, profils: &HashMap<String, ProfilIa>) -> Result<Self, String> {
+        let nom = section.nom["ennemi.".len()..].to_string();
+
+        let nom_profil = section.get("profil").unwrap_or(nom.as_str());
+        let profil = profils.get(nom_profil)
+            .cloned()
+            .ok_or(format!("{} : profil d'ia '{}' inconnu pour l'ennemi '{}'", path, nom_profil, nom))?;
+
+        let biomes = section.get_list("biomes")
+            .iter()
+            .map(|code| TileType::from_code(code).ok_or(format!("{} : biome inconnu '{}' pour l'ennemi '{}'", path, code, nom)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let capacite = match section.get("capacite").unwrap_or("aucune") {
+            "aucune" => Capacite::Aucune,
+            "tireur" => Capacite::Tireur {
+                portee: section.get_or("portee", 200.0),
+                cadence: section.get_or("cadence", 1.5),
+                vitesse_projectile: section.get_or("vitesse_projectile", 250.0),
+                duree_projectile: section.get_or("duree_projectile", 1.5),
+                son: section.get_or("son", "arme".to_string())
+            },
+            "fouisseur" => Capacite::Fouisseur {
+                distance_surgissement: section.get_or("distance_surgissement", 96.0)
+            },
+            autre => return Err(format!("{} : capacite inconnue '{}' pour l'ennemi '{}'", path, autre, nom))
+        };
+
+        let taille_sprite = section.get_or("taille_sprite", 128);
+
+        Ok(Self {
+            sprite: section.get_or("sprite", "croco".to_string()),
+            taille_sprite: Vecteur2D::new(taille_sprite, taille_sprite),
+            taille_affichage: section.get_or("taille_affichage", 32),
+            rayon: section.get_or("rayon", 16.0),
+            points_de_vie: section.get_or("points_de_vie", 1.0),
+            degats: section.get_or("degats", 1.0),
+            recul: section.get_or("recul", 300.0),
+            profil,
+            capacite,
+            biomes,
+            butin: section.get_or("butin", "ennemi".to_string()),
+            points: section.get_or("points", 10),
+            poids: section.get_or("poids", 1.0),
+            nom
+        })
+    }
+
+    pub fn apparait_sur(&self, biome: &TileType) -> bool {
+        self.biomes.contains(biome)
+    }
+}
+
+// tous les archetypes du jeu, partages entre les ennemis
+pub struct ArchetypeRegistry {
+    archetypes: Vec<Rc<Archetype>>
+}
+
+impl ArchetypeRegistry {
+    pub fn load(path: &str, profils: &HashMap<String, ProfilIa>) -> Result<Self, String> {
+        let archetypes = IniFile::load(path)?
+            .sections_prefixees("ennemi.")
+            .into_iter()
+            .map(|section| Archetype::from_section(path, section, profils).map(Rc::new))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        if archetypes.is_empty() {
+            return Err(format!("{} : aucun ennemi defini", path));
+        }
+
+        Ok(Self { archetypes })
+    }
+
+    pub fn get(&self, nom: &str) -> Option<Rc<Archetype>> {
+        self.archetypes.iter().find(|archetype| archetype.nom == nom).cloned()
+    }
+
+    // tirage pondere parmi les archetypes qui vivent sur ce biome, None si aucun
+    pub fn tirer_pour_biome<R: Rng + ?Sized>(&self, biome: &TileType, rng: &mut R) -> Option<Rc<Archetype>> {
+        let candidats = self.archetypes.iter()
+            .filter(|archetype| archetype.apparait_sur(biome) && archetype.poids > 0.0)
+            .collect::<Vec<_>>();
+
+        if candidats.is_empty() {
+            return None;
+        }
+        let total = candidats.iter().map(|archetype| archetype.poids).sum::<f32>();
+
+        let mut tirage = rng.gen_range(0.0..total);
+        candidats.iter()
+            .find(|archetype| {
+                tirage -= archetype.poids;
+                tirage < 0.0
+            })
+            .or(candidats.last())
+            .map(|archetype| Rc::clone(archetype))
+    }
+}
@@ -1,54 +1,281 @@
+use std::rc::Rc;
+
+use crate::core::elements::tilemap::tile::TileType;
 use crate::core::elements::tilemap::TileMapHudge;
+use crate::core::pathfinding::{Pathfinder, ResultatChemin};
 use crate::core::physics::collide_body::CollideBody;
+use crate::core::scene::scene_world::archetype::{Archetype, Capacite};
+use crate::core::scene::scene_world::enemy_ai::{ligne_de_vue, point_autour, EtatIa};
+use crate::core::scene::scene_world::health::{Degats, Sante};
 use crate::core::scene::scene_world::player::Player;
+use crate::core::scene::scene_world::projectile::Projectile;
 use crate::core::sdd::vecteur2d::Vecteur2D;
 
+const INTERVALLE_REPLANIFICATION: f32 = 0.5; // secondes entre deux recherches si la cible change de case
+const DUREE_TRAQUE: f32 = 15.0; // secondes pour rejoindre le joueur en arrivant avec une vague
+
+enum Deplacement {
+    EnRoute,
+    Arrive,
+    Bloque // pas de chemin vers la cible
+}
+
+// ce que l'ennemi demande a la scene pendant sa mise a jour
+pub enum EvenementEnnemi {
+    Tir { projectile: Projectile, son: String }
+}
+
 #[derive(Clone)]
 pub struct Enemy {
     pub collide_body: CollideBody,
-    distance_attaque: f32,
-    vitesse: f32
+    pub a_repere_joueur: bool,
+    pub etat: EtatIa,
+    pub sante: Sante,
+    pub archetype: Rc<Archetype>,
+    maison: Vecteur2D<f32>, // point d'apparition, l'ennemi y revient apres une poursuite
+    derniere_position_connue: Vecteur2D<f32>,
+    chemin: Vec<Vecteur2D<i32>>, // cases restantes vers la cible
+    cible_chemin: Option<(i32, i32)>,
+    delai_replanification: f32,
+    recharge_tir: f32
 }
 
 impl Enemy {
-    pub fn new(position: Vecteur2D<f32>) -> Self {
+    pub fn new(position: Vecteur2D<f32>, archetype: Rc<Archetype>) -> Self {
+        let etat = match archetype.capacite {
+            Capacite::Fouisseur { .. } => EtatIa::Enfoui,
+            _ => EtatIa::Repos { reste: archetype.profil.duree_repos() }
+        };
+
         Self {
             collide_body: CollideBody::basic(
-                position, 16f32
+                position.clone(), archetype.rayon
             ),
-            distance_attaque: 300.0,
-            vitesse: 20.0
+            a_repere_joueur: false,
+            etat,
+            sante: Sante::new(archetype.points_de_vie, 0.0),
+            archetype,
+            maison: position.clone(),
+            derniere_position_connue: position,
+            chemin: vec![],
+            cible_chemin: None,
+            delai_replanification: 0.0,
+            recharge_tir: 0.0
         }
     }
 
+    pub fn est_en_chasse(&self) -> bool {
+        matches!(self.etat, EtatIa::Alerte { .. } | EtatIa::Poursuite)
+    }
+
+    // arrive avec une vague : se dirige vers la position du joueur sans l'avoir vu
+    pub fn traquer(&mut self, cible: &Vecteur2D<f32>) {
+        self.derniere_position_connue = cible.clone();
+        self.etat = EtatIa::PerteDeVue { reste: DUREE_TRAQUE };
+    }
+
+    // coup porte au joueur au contact
+    pub fn degats_contact(&self, joueur: &Vecteur2D<f32>) -> Degats {
+        Degats {
+            montant: self.archetype.degats,
+            direction: Vecteur2D::<f32>::from_points(&self.collide_body.position, joueur)
+                .unitaire()
+                .unwrap_or(Vecteur2D::new(0.0, 0.0)),
+            recul: self.archetype.recul
+        }
+    }
+
+    // un fouisseur enfoui n'est ni affiche ni touchable
+    pub fn est_visible(&self) -> bool {
+        !matches!(self.etat, EtatIa::Enfoui)
+    }
+
+    pub fn update(&mut self, dt: f32, tilemap: &TileMapHudge, joueur: &Player, pathfinder: &mut Pathfinder) -> Option<EvenementEnnemi> {
+        let distance_joueur = Vecteur2D::<f32>::from_points(&self.collide_body.position, &joueur.pos).norme();
+        let voit_joueur = distance_joueur < self.archetype.profil.distance_vue * (1.0 - joueur.discretion)
+            && ligne_de_vue(tilemap, &self.collide_body.position, &joueur.pos);
+
+        if voit_joueur {
+            self.derniere_position_connue = joueur.pos.clone();
+        }
+
+        let recul = self.sante.update(dt);
+        self.collide_body.deplacer_si_libre(&recul, tilemap, &tilemap.generateur.tuiles_bloquantes());
 
-    pub fn update(&mut self, dt: f32, _tilemap: &TileMapHudge, joueur: &Player) {
+        self.recharge_tir -= dt;
+        let mut evenement = None;
 
-        let vec_enemie_joueur = Vecteur2D::<f32>::from_points(
-            &self.collide_body.position,
-            &joueur.pos);
+        let archetype = Rc::clone(&self.archetype);
+        let profil = &archetype.profil;
+
+        self.etat = match self.etat.clone() {
+            EtatIa::Enfoui => match archetype.capacite {
+                Capacite::Fouisseur { distance_surgissement } if voit_joueur && distance_joueur < distance_surgissement => {
+                    EtatIa::Alerte { reste: profil.duree_alerte }
+                }
+                _ => EtatIa::Enfoui
+            },
+
+            EtatIa::Repos { .. } | EtatIa::Errance { .. } | EtatIa::Retour if voit_joueur => {
+                EtatIa::Alerte { reste: profil.duree_alerte }
+            }
 
-        let distance_enemi_joueur = vec_enemie_joueur
-            .norme();
+            EtatIa::Repos { reste } if reste - dt <= 0.0 => {
+                EtatIa::Errance { destination: point_autour(&self.maison, profil.rayon_errance) }
+            }
+            EtatIa::Repos { reste } => EtatIa::Repos { reste: reste - dt },
+
+            EtatIa::Errance { destination } => match self.aller_vers(dt, tilemap, &destination, profil.vitesse_errance, pathfinder) {
+                Deplacement::EnRoute => EtatIa::Errance { destination },
+                _ => EtatIa::Repos { reste: profil.duree_repos() }
+            },
+
+            EtatIa::Alerte { .. } if !voit_joueur => self.commencer_recherche(),
+            EtatIa::Alerte { reste } if reste - dt <= 0.0 => {
+                self.a_repere_joueur = true;
+                EtatIa::Poursuite
+            }
+            EtatIa::Alerte { reste } => EtatIa::Alerte { reste: reste - dt },
+
+            EtatIa::Poursuite if self.trop_loin_de_la_maison() => EtatIa::Retour,
+            EtatIa::Poursuite if voit_joueur => {
+                match &archetype.capacite {
+                    // a portee : reste sur place et tire
+                    Capacite::Tireur { portee, cadence, vitesse_projectile, duree_projectile, son } if distance_joueur <= *portee => {
+                        if self.recharge_tir <= 0.0 {
+                            self.recharge_tir = *cadence;
+                            evenement = Vecteur2D::<f32>::from_points(&self.collide_body.position, &joueur.pos)
+                                .unitaire()
+                                .map(|direction| EvenementEnnemi::Tir {
+                                    projectile: Projectile::new(
+                                        self.collide_body.position.clone(),
+                                        Vecteur2D::new(direction.x * vitesse_projectile, direction.y * vitesse_projectile),
+                                        archetype.degats,
+                                        archetype.recul,
+                                        *duree_projectile,
+                                        false
+                                    ),
+                                    son: son.clone()
+                                });
+                        }
+                    }
+                    _ => {
+                        self.aller_vers(dt, tilemap, &joueur.pos, profil.vitesse_poursuite, pathfinder);
+                    }
+                }
+                EtatIa::Poursuite
+            }
+            EtatIa::Poursuite => EtatIa::PerteDeVue { reste: profil.duree_perte_de_vue },
 
-        if self.distance_attaque > distance_enemi_joueur {
-            // se deplace (attaque) vers le joueur
-            let unitaire_opt = vec_enemie_joueur.unitaire();
+            // deja en alerte : repart directement en poursuite
+            EtatIa::PerteDeVue { .. } | EtatIa::Recherche { .. } if voit_joueur => EtatIa::Poursuite,
 
-            match unitaire_opt {
-                Some(unitaire) => {
-                    let deplacement_v = Vecteur2D::new(
-                        unitaire.x * self.vitesse * dt,
-                        unitaire.y * self.vitesse * dt
-                    );
-                    self.collide_body.position += deplacement_v;
+            EtatIa::PerteDeVue { reste } => {
+                let cible = self.derniere_position_connue.clone();
+                match self.aller_vers(dt, tilemap, &cible, profil.vitesse_poursuite, pathfinder) {
+                    Deplacement::EnRoute if reste - dt > 0.0 => EtatIa::PerteDeVue { reste: reste - dt },
+                    _ => self.commencer_recherche()
                 }
-                None => ()
             }
 
-        } else {
-            // ne fait rien (est dans un etat bete pour le moment)
+            EtatIa::Recherche { reste, .. } if reste - dt <= 0.0 => EtatIa::Retour,
+            EtatIa::Recherche { reste, point } => {
+                let point = match self.aller_vers(dt, tilemap, &point, profil.vitesse_errance, pathfinder) {
+                    Deplacement::EnRoute => point,
+                    _ => point_autour(&self.derniere_position_connue, profil.rayon_recherche)
+                };
+                EtatIa::Recherche { reste: reste - dt, point }
+            }
+
+            EtatIa::Retour => {
+                let maison = self.maison.clone();
+                match self.aller_vers(dt, tilemap, &maison, profil.vitesse_errance, pathfinder) {
+                    Deplacement::EnRoute => EtatIa::Retour,
+                    _ if self.peut_s_enfouir(tilemap) => EtatIa::Enfoui,
+                    _ => EtatIa::Repos { reste: profil.duree_repos() }
+                }
+            }
+        };
+
+        evenement
+    }
+
+    // le fouisseur rentre sous le goo une fois revenu chez lui
+    fn peut_s_enfouir(&self, tilemap: &TileMapHudge) -> bool {
+        matches!(self.archetype.capacite, Capacite::Fouisseur { .. })
+            && tilemap.get_tile_from_position(&self.collide_body.position)
+                .map(|tile| tile.r#type == TileType::Goo)
+                .unwrap_or(false)
+    }
+
+    fn commencer_recherche(&self) -> EtatIa {
+        EtatIa::Recherche {
+            reste: self.archetype.profil.duree_recherche,
+            point: point_autour(&self.derniere_position_connue, self.archetype.profil.rayon_recherche)
         }
-        // todo update enemies
     }
-}
\ No newline at end of file
+
+    fn trop_loin_de_la_maison(&self) -> bool {
+        Vecteur2D::<f32>::from_points(&self.maison, &self.collide_body.position).norme() > self.archetype.profil.distance_abandon
+    }
+
+    // suit le chemin calcule par le pathfinder (contourne les murs)
+    fn aller_vers(&mut self, dt: f32, tilemap: &TileMapHudge, cible: &Vecteur2D<f32>, vitesse: f32, pathfinder: &mut Pathfinder) -> Deplacement {
+        let case_cible = tilemap.get_tile_index_from_position(cible);
+        let case_enemy = tilemap.get_tile_index_from_position(&self.collide_body.position);
+
+        self.delai_replanification -= dt;
+        let cible_changee = self.cible_chemin != Some((case_cible.x, case_cible.y));
+
+        if self.chemin.is_empty() || (cible_changee && self.delai_replanification <= 0.0) {
+            match pathfinder.chemin(tilemap, &case_enemy, &case_cible) {
+                ResultatChemin::Chemin(chemin) => {
+                    self.chemin = chemin;
+                    self.cible_chemin = Some((case_cible.x, case_cible.y));
+                    self.delai_replanification = INTERVALLE_REPLANIFICATION;
+                }
+                ResultatChemin::Introuvable => {
+                    self.chemin.clear();
+                    self.cible_chemin = None;
+                    return Deplacement::Bloque;
+                }
+                ResultatChemin::EnAttente if self.chemin.is_empty() => return Deplacement::EnRoute, // attend la frame suivante
+                ResultatChemin::EnAttente => () // garde l'ancien chemin en attendant
+            }
+        }
+
+        // dans la case de la cible : va droit dessus
+        let prochain_point = match self.chemin.first() {
+            Some(case) => Vecteur2D::new(
+                (case.x as f32 + 0.5) * tilemap.tile_size as f32,
+                (case.y as f32 + 0.5) * tilemap.tile_size as f32
+            ),
+            None => cible.clone()
+        };
+
+        // le corps glisse le long des murs : un gros ennemi ne coupe pas les angles entre deux cases du chemin
+        let vers_point = Vecteur2D::<f32>::from_points(&self.collide_body.position, &prochain_point);
+        let pas = vitesse * dt;
+        let bloquantes = tilemap.generateur.tuiles_bloquantes();
+
+        if vers_point.norme() <= pas {
+            self.collide_body.deplacer_si_libre(&vers_point, tilemap, &bloquantes);
+            if self.chemin.is_empty() {
+                return Deplacement::Arrive;
+            }
+            self.chemin.remove(0);
+        } else if let Some(unitaire) = vers_point.unitaire() {
+            let avant = self.collide_body.position.clone();
+            self.collide_body.deplacer_si_libre(&Vecteur2D::new(unitaire.x * pas, unitaire.y * pas), tilemap, &bloquantes);
+
+            // trop gros pour le passage : le chemin est abandonne
+            if (self.collide_body.position.x, self.collide_body.position.y) == (avant.x, avant.y) {
+                self.chemin.clear();
+                self.cible_chemin = None;
+                return Deplacement::Bloque;
+            }
+        }
+
+        Deplacement::EnRoute
+    }
+}
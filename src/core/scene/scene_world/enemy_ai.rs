@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::elements::tilemap::TileMapHudge;
+use crate::core::physics::raycast::raycast;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const AI_PATH: &str = "assets/data/ai.ini";
+
+// parametres de comportement d'un ennemi (cf ai.ini), distances en pixels et durees en secondes
+#[derive(Clone)]
+pub struct ProfilIa {
+    pub distance_vue: f32,
+    pub duree_alerte: f32,
+    pub vitesse_errance: f32,
+    pub vitesse_poursuite: f32,
+    pub rayon_errance: f32,
+    pub repos_min: f32,
+    pub repos_max: f32,
+    pub duree_perte_de_vue: f32,
+    pub duree_recherche: f32,
+    pub rayon_recherche: f32,
+    pub distance_abandon: f32 // trop loin de chez lui, l'ennemi abandonne la poursuite
+}
+
+impl ProfilIa {
+    fn from_section(section: &IniSection) -> Self {
+        Self {
+            distance_vue: section.get_or("distance_vue", 300.0),
+            duree_alerte: section.get_or("duree_alerte", 0.5),
+            vitesse_errance: section.get_or("vitesse_errance", 12.0),
+            vitesse_poursuite: section.get_or("vitesse_poursuite", 20.0),
+            rayon_errance: section.get_or("rayon_errance", 96.0),
+            repos_min: section.get_or("repos_min", 1.0),
+            repos_max: section.get_or("repos_max", 3.0),
+            duree_perte_de_vue: section.get_or("duree_perte_de_vue", 1.5),
+            duree_recherche: section.get_or("duree_recherche", 5.0),
+            rayon_recherche: section.get_or("rayon_recherche", 96.0),
+            distance_abandon: section.get_or("distance_abandon", 700.0)
+        }
+    }
+
+    pub fn load_all(path: &str) -> Result<HashMap<String, Self>, String> {
+        Ok(
+            IniFile::load(path)?
+                .sections_prefixees("profil.")
+                .into_iter()
+                .map(|section| (section.nom["profil.".len()..].to_string(), Self::from_section(section)))
+                .collect::<HashMap<_, _>>()
+        )
+    }
+
+    pub fn duree_repos(&self) -> f32 {
+        if self.repos_max > self.repos_min {
+            rand::thread_rng().gen_range(self.repos_min..self.repos_max)
+        } else {
+            self.repos_min
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum EtatIa {
+    Repos { reste: f32 },
+    Errance { destination: Vecteur2D<f32> },
+    Alerte { reste: f32 }, // a vu le joueur, marque un temps avant de foncer
+    Poursuite,
+    PerteDeVue { reste: f32 }, // continue vers la derniere position connue
+    Recherche { reste: f32, point: Vecteur2D<f32> }, // fouille autour de la derniere position connue
+    Retour,
+    Enfoui // fouisseur cache sous le goo, attend que le joueur approche
+}
+
+// point au hasard dans un disque
+pub fn point_autour(centre: &Vecteur2D<f32>, rayon: f32) -> Vecteur2D<f32> {
+    let mut rng = rand::thread_rng();
+    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+    let distance = rng.gen_range(0.0..=rayon);
+
+    Vecteur2D::new(centre.x + angle.cos() * distance, centre.y + angle.sin() * distance)
+}
+
+// la vue est coupee par les tuiles bloquantes du monde et par les chunks non charges
+pub fn ligne_de_vue(tilemap: &TileMapHudge, de: &Vecteur2D<f32>, vers: &Vecteur2D<f32>) -> bool {
+    raycast(tilemap, de, vers, &tilemap.generateur.tuiles_bloquantes()).is_none()
+}
@@ -0,0 +1,76 @@
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+const AMORTI_RECUL: f32 = 10.0; // perte de vitesse du recul par seconde (exponentielle)
+const PERIODE_CLIGNOTEMENT: f32 = 0.1; // secondes
+
+// coup recu : la direction va de la source vers la cible (unitaire), le recul est une vitesse en pixels par seconde
+pub struct Degats {
+    pub montant: f32,
+    pub direction: Vecteur2D<f32>,
+    pub recul: f32
+}
+
+// points de vie, fenetre d'invulnerabilite apres un coup et recul en cours
+#[derive(Clone)]
+pub struct Sante {
+    pub points: f32,
+    pub max: f32,
+    pub duree_invulnerabilite: f32,
+    invulnerable: f32, // temps d'invulnerabilite restant
+    recul: Vecteur2D<f32>
+}
+
+impl Sante {
+    pub fn new(max: f32, duree_invulnerabilite: f32) -> Self {
+        Self {
+            points: max,
+            max,
+            duree_invulnerabilite,
+            invulnerable: 0.0,
+            recul: Vecteur2D::new(0.0, 0.0)
+        }
+    }
+
+    // false si le coup est ignore (invulnerable ou deja mort)
+    pub fn subir(&mut self, degats: &Degats) -> bool {
+        if self.est_invulnerable() || self.est_mort() {
+            return false;
+        }
+
+        self.points = (self.points - degats.montant).max(0.0);
+        self.invulnerable = self.duree_invulnerabilite;
+        self.recul = Vecteur2D::new(degats.direction.x * degats.recul, degats.direction.y * degats.recul);
+        true
+    }
+
+    pub fn soigner(&mut self, points: f32) {
+        self.points = (self.points + points).min(self.max);
+    }
+
+    // deplacement du au recul pour cette frame
+    pub fn update(&mut self, dt: f32) -> Vecteur2D<f32> {
+        self.invulnerable = (self.invulnerable - dt).max(0.0);
+
+        let deplacement = Vecteur2D::new(self.recul.x * dt, self.recul.y * dt);
+        let amorti = (1.0 - AMORTI_RECUL * dt).max(0.0);
+        self.recul = Vecteur2D::new(self.recul.x * amorti, self.recul.y * amorti);
+        deplacement
+    }
+
+    pub fn est_mort(&self) -> bool {
+        self.points <= 0.0
+    }
+
+    pub fn est_invulnerable(&self) -> bool {
+        self.invulnerable > 0.0
+    }
+
+    // pendant l'invulnerabilite, le sprite n'est affiche qu'une periode sur deux
+    pub fn visible(&self) -> bool {
+        !self.est_invulnerable() || (self.invulnerable / PERIODE_CLIGNOTEMENT).floor() % 2.0 == 0.0
+    }
+
+    pub fn ratio(&self) -> f32 {
+        if self.max > 0.0 { self.points / self.max } else { 0.0 }
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::scene::scene_world::pickup::EffetObjet;
+
+pub const ITEMS_PATH: &str = "assets/data/items.ini";
+
+// bonus d'un equipement, retire quand il est enleve
+#[derive(Clone)]
+pub struct BonusEquipement {
+    pub emplacement: String, // un seul equipement par emplacement
+    pub vitesse: f32,
+    pub points_de_vie: f32
+}
+
+#[derive(Clone)]
+pub enum UsageItem {
+    Consommable(EffetObjet),
+    Equipement(BonusEquipement)
+}
+
+// objet d'inventaire defini dans items.ini
+pub struct DefinitionItem {
+    pub nom: String,
+    pub pile_max: u32,
+    pub usage: UsageItem
+}
+
+impl DefinitionItem {
+    fn from_section(path: &str, section: &IniSection) -> Result<Self, String> {
+        let nom = section.nom["item.".len()..].to_string();
+
+        let usage = match section.get("usage").unwrap_or("") {
+            "soin" => UsageItem::Consommable(EffetObjet::Soin(section.get_or("valeur", 1.0))),
+            "munitions" => UsageItem::Consommable(EffetObjet::Munitions(section.get_or("valeur", 1))),
+            "equipement" => UsageItem::Equipement(BonusEquipement {
+                emplacement: section.get("emplacement")
+                    .ok_or(format!("{} : emplacement absent pour l'item '{}'", path, nom))?
+                    .to_string(),
+                vitesse: section.get_or("vitesse", 0.0),
+                points_de_vie: section.get_or("points_de_vie", 0.0)
+            }),
+            autre => return Err(format!("{} : usage inconnu '{}' pour l'item '{}'", path, autre, nom))
+        };
+
+        Ok(Self {
+            pile_max: section.get_or::<u32>("pile_max", 1).max(1),
+            usage,
+            nom
+        })
+    }
+
+    pub fn load_all(path: &str) -> Result<HashMap<String, Rc<Self>>, String> {
+        IniFile::load(path)?
+            .sections_prefixees("item.")
+            .into_iter()
+            .map(|section| Self::from_section(path, section).map(|item| (item.nom.clone(), Rc::new(item))))
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+pub struct Pile {
+    pub definition: Rc<DefinitionItem>,
+    pub quantite: u32
+}
+
+// cases d'inventaire (une pile par case) et equipements portes
+#[derive(Clone)]
+pub struct Inventaire {
+    pub capacite: usize,
+    pub piles: Vec<Pile>,
+    pub equipes: Vec<Rc<DefinitionItem>>
+}
+
+impl Inventaire {
+    pub fn new(capacite: usize) -> Self {
+        Self { capacite, piles: vec![], equipes: vec![] }
+    }
+
+    // complete les piles existantes puis ouvre de nouvelles cases, renvoie la quantite qui n'a pas trouve de place
+    pub fn ajouter(&mut self, definition: &Rc<DefinitionItem>, quantite: u32) -> u32 {
+        let mut reste = quantite;
+
+        self.piles.iter_mut()
+            .filter(|pile| Rc::ptr_eq(&pile.definition, definition))
+            .for_each(|pile| {
+                let ajout = (definition.pile_max - pile.quantite.min(definition.pile_max)).min(reste);
+                pile.quantite += ajout;
+                reste -= ajout;
+            });
+
+        while reste > 0 && self.piles.len() < self.capacite {
+            let ajout = definition.pile_max.min(reste);
+            self.piles.push(Pile { definition: Rc::clone(definition), quantite: ajout });
+            reste -= ajout;
+        }
+
+        reste
+    }
+
+    // une pile videe libere sa case
+    pub fn retirer(&mut self, index: usize, quantite: u32) {
+        if let Some(pile) = self.piles.get_mut(index) {
+            pile.quantite = pile.quantite.saturating_sub(quantite);
+            if pile.quantite == 0 {
+                self.piles.remove(index);
+            }
+        }
+    }
+
+    pub fn equipe_sur(&self, emplacement: &str) -> Option<usize> {
+        self.equipes.iter().position(|item| match &item.usage {
+            UsageItem::Equipement(bonus) => bonus.emplacement == emplacement,
+            UsageItem::Consommable(_) => false
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    const NB_GRAINES: u64 = 50;
+    const OPERATIONS_PAR_GRAINE: usize = 200;
+
+    fn item(nom: &str, pile_max: u32) -> Rc<DefinitionItem> {
+        Rc::new(DefinitionItem {
+            nom: nom.to_string(),
+            pile_max,
+            usage: UsageItem::Consommable(EffetObjet::Soin(1.0))
+        })
+    }
+
+    fn quantites(inventaire: &Inventaire) -> Vec<(String, u32)> {
+        inventaire.piles.iter().map(|pile| (pile.definition.nom.clone(), pile.quantite)).collect()
+    }
+
+    #[test]
+    fn ajouter_complete_les_piles_avant_d_ouvrir_des_cases() {
+        let trousse = item("trousse", 5);
+        let bottes = item("bottes", 1);
+        let mut inventaire = Inventaire::new(4);
+
+        assert_eq!(inventaire.ajouter(&trousse, 7), 0);
+        assert_eq!(inventaire.ajouter(&bottes, 1), 0);
+        assert_eq!(inventaire.ajouter(&trousse, 4), 0);
+
+        assert_eq!(quantites(&inventaire), vec![
+            ("trousse".to_string(), 5),
+            ("trousse".to_string(), 5),
+            ("bottes".to_string(), 1),
+            ("trousse".to_string(), 1)
+        ]);
+    }
+
+    #[test]
+    fn ajouter_renvoie_ce_qui_deborde() {
+        let trousse = item("trousse", 5);
+        let mut inventaire = Inventaire::new(2);
+
+        assert_eq!(inventaire.ajouter(&trousse, 13), 3);
+        assert_eq!(inventaire.ajouter(&item("bottes", 1), 1), 1);
+        assert_eq!(inventaire.ajouter(&trousse, 1), 1);
+        assert_eq!(quantites(&inventaire), vec![("trousse".to_string(), 5), ("trousse".to_string(), 5)]);
+    }
+
+    #[test]
+    fn retirer_libere_la_case_videe() {
+        let trousse = item("trousse", 5);
+        let mut inventaire = Inventaire::new(3);
+        inventaire.ajouter(&trousse, 7);
+
+        inventaire.retirer(0, 2);
+        assert_eq!(quantites(&inventaire), vec![("trousse".to_string(), 3), ("trousse".to_string(), 2)]);
+
+        inventaire.retirer(1, 10);
+        inventaire.retirer(5, 1);
+        assert_eq!(quantites(&inventaire), vec![("trousse".to_string(), 3)]);
+    }
+
+    // suite d'ajouts et de retraits au hasard : jamais plus de cases que la capacite ni de pile trop pleine,
+    // et rien ne se perd en dehors de ce qui deborde
+    #[test]
+    fn ajouts_et_retraits_au_hasard_conservent_les_quantites() {
+        (0..NB_GRAINES).for_each(|graine| {
+            let mut rng = StdRng::seed_from_u64(graine);
+            let items = [item("trousse", 5), item("caisse", 3), item("bottes", 1)];
+            let mut inventaire = Inventaire::new(rng.gen_range(1..=6));
+            let mut attendu = 0u32;
+
+            (0..OPERATIONS_PAR_GRAINE).for_each(|_| {
+                if rng.gen_bool(0.6) || inventaire.piles.is_empty() {
+                    let quantite = rng.gen_range(1..=8);
+                    attendu += quantite - inventaire.ajouter(&items[rng.gen_range(0..items.len())], quantite);
+                } else {
+                    let index = rng.gen_range(0..inventaire.piles.len());
+                    let quantite = rng.gen_range(1..=4);
+                    attendu -= quantite.min(inventaire.piles[index].quantite);
+                    inventaire.retirer(index, quantite);
+                }
+
+                assert!(inventaire.piles.len() <= inventaire.capacite, "graine {}", graine);
+                assert!(
+                    inventaire.piles.iter().all(|pile| pile.quantite >= 1 && pile.quantite <= pile.definition.pile_max),
+                    "graine {}", graine
+                );
+                assert_eq!(inventaire.piles.iter().map(|pile| pile.quantite).sum::<u32>(), attendu, "graine {}", graine);
+            });
+        });
+    }
+}
@@ -0,0 +1,29 @@
+use crate::core::data::ini::{IniFile, IniSection};
+
+// attaque au corps a corps (section [corps_a_corps] de weapons.ini)
+#[derive(Clone)]
+pub struct AttaqueMelee {
+    pub portee: f32, // pixels depuis le centre du joueur
+    pub ouverture: f32, // angle total de l'arc, en degres
+    pub degats: f32,
+    pub recul: f32,
+    pub recharge: f32, // secondes entre deux coups
+    pub duree_effet: f32 // duree d'affichage de l'arc
+}
+
+impl AttaqueMelee {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let fichier = IniFile::load(path)?;
+        let defaut = IniSection::new("corps_a_corps");
+        let section = fichier.section("corps_a_corps").unwrap_or(&defaut);
+
+        Ok(Self {
+            portee: section.get_or("portee", 56.0),
+            ouverture: section.get_or("ouverture", 120.0),
+            degats: section.get_or("degats", 2.0),
+            recul: section.get_or("recul", 350.0),
+            recharge: section.get_or("recharge", 0.5),
+            duree_effet: section.get_or("duree_effet", 0.15)
+        })
+    }
+}
@@ -1,6 +1,18 @@
 pub mod scene_world_data;
 pub mod player;
 pub mod enemy;
+pub mod archetype;
+pub mod enemy_ai;
+pub mod save;
+pub mod run_state;
+pub mod health;
+pub mod projectile;
+pub mod weapon;
+pub mod melee;
+pub mod pickup;
+pub mod inventory;
+pub mod spawner;
+pub mod upgrade;
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -11,12 +23,21 @@ use crate::core::graphics::models::color::Color;
 use crate::core::graphics::{CanDrawSprite, CanDrawText};
 use crate::core::input::CanManageInput;
 use crate::core::musics::CanPlayMusic;
-use crate::core::physics::collide_body::{CanCollideWithTileMapHudge};
+use crate::core::physics::collide_body::{CanCollideWithTileMapHudge, CollideBody};
+use crate::core::physics::shapes::ArcDeCercle;
 use crate::core::scene::{SceneEnum};
+use crate::core::scene::scene_amelioration::SceneAmelioration;
 use crate::core::scene::scene_game_over::SceneGameOver;
-use crate::core::scene::scene_world::enemy::Enemy;
+use crate::core::scene::scene_inventaire::SceneInventaire;
+use crate::core::scene::scene_menu::SceneMenu;
+use crate::core::scene::scene_world::enemy::{Enemy, EvenementEnnemi};
+use crate::core::scene::scene_world::health::Degats;
+use crate::core::scene::scene_world::pickup::Objet;
+use crate::core::scene::scene_world::projectile::RAYON_PROJECTILE;
+use crate::core::scene::scene_world::run_state::RunState;
 use crate::core::scene::scene_world::scene_world_data::SceneWorldData;
 use crate::core::sdd::vecteur2d::Vecteur2D;
+use crate::core::translations::TranslationService;
 
 pub struct SceneWorld<SpriteService, TextService, InputService, MusicService>
     where
@@ -29,6 +50,7 @@ pub struct SceneWorld<SpriteService, TextService, InputService, MusicService>
     pub text_service: Rc<RefCell<TextService>>,
     pub sprite_service: Rc<RefCell<SpriteService>>,
     pub music_service: Rc<RefCell<MusicService>>,
+    pub translation_service: Rc<RefCell<TranslationService>>,
     pub data: SceneWorldData
 }
 
@@ -45,18 +67,27 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
     ) -> Option<SceneEnum<SpriteService, TextService, InputService, MusicService>> {
 
         self.init_scene().expect("erreur lors de l'initialisation de la scene");
+        self.data.run.temps_total += dt;
 
         self.update_player(dt).expect("erreur lors de l'update du player");
+        self.data.tilemap.update_chunks(&self.data.player.pos);
+        self.update_spawner(dt);
         self.update_enemies(dt);
         self.update_curseur();
+        self.update_inventaire_armes();
+        self.update_tir(dt);
+        self.update_melee(dt);
+        self.update_projectiles(dt);
+        self.retirer_ennemis_morts();
+        self.update_degats_joueur();
+        self.update_objets();
         self.update_camera();
-        self.test_play_sound();
+        self.update_listener();
+        self.update_music().expect("erreur lors de la mise a jour de la musique");
+        self.update_debug();
 
-        self.draw_near_tilemaps().expect("erreur lors de l'affichage de la map");
-        self.draw_vaisseau_a_trouver().expect("erreur lors de l'affichage du vaisseau");
-        self.draw_player().expect("erreur lors de l'affichage du player");
-        self.draw_enemies().expect("erreur lors de l'affichage du player");
-        self.draw_cursor().expect("erreur lors de l'affichage du curseur");
+        self.draw_monde();
+        self.draw_barre_de_vie().expect("erreur lors de l'affichage de la barre de vie");
 
         let _keys_pressed = self.get_keys_pressed();
         let _mouse_key_pressed = self.get_mouse_keys_pressed();
@@ -72,10 +103,19 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
         ).expect("erreur affichage panel");
 
 
-        vec![
-            format!("count enemies = {}", self.data.enemies.len()),
-            format!("lvl {}", self.data.compteur_de_monde_genere)
-        ]
+        let traductions = self.translation_service.borrow();
+        let mut lignes_hud = vec![
+            traductions.tr_args("monde.nb_ennemis", &[("n", self.data.enemies.len().to_string())]),
+            traductions.tr_args("monde.niveau", &[("n", self.data.run.compteur_de_monde.to_string())]),
+            self.ligne_arme(),
+            traductions.tr_args("monde.monnaie", &[("n", self.data.run.monnaie.to_string())]),
+            traductions.tr_args("monde.score", &[("n", self.data.run.score.to_string())])
+        ];
+        if self.data.affiche_zones_apparition {
+            lignes_hud.push(traductions.tr("monde.debug_apparition"));
+        }
+
+        lignes_hud
             .iter()
             .enumerate()
             .for_each(|(index, debug_str)| {
@@ -87,20 +127,56 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
                     Color::rgb(255u8, 0u8, 255u8)
                 ).expect("erreur lors de l'affichage");
             });
+        drop(traductions);
 
-        if self.data.vaisseau_a_trouver.is_collide_with_object(&self.data.player.pos, 16.0) {
+        if self.ouvre_inventaire() {
+            Some(
+                SceneEnum::SceneInventaire(Box::new(
+                    SceneInventaire::new(
+                        Rc::clone(&self.input_service),
+                        Rc::clone(&self.text_service),
+                        Rc::clone(&self.sprite_service),
+                        Rc::clone(&self.music_service),
+                        Rc::clone(&self.translation_service)
+                    )
+                ))
+            )
+        } else if self.quitte_vers_menu() {
+            // sauvegarde impossible (dossier en lecture seule, disque plein) : retour au menu quand meme
+            if let Err(erreur) = self.data.save() {
+                eprintln!("erreur lors de la sauvegarde de la partie : {}", erreur);
+            }
+            Some(
+                SceneEnum::SceneMenu(
+                    SceneMenu::new(
+                        Rc::clone(&self.input_service),
+                        Rc::clone(&self.text_service),
+                        Rc::clone(&self.sprite_service),
+                        Rc::clone(&self.music_service),
+                        Rc::clone(&self.translation_service)
+                    )
+                )
+            )
+        } else if self.data.vaisseau_a_trouver.is_collide_with_object(&self.data.player.pos, 16.0) {
+            let mut run = self.data.etat_de_la_partie();
+            run.ennemis_evites += self.data.enemies.iter().filter(|enemy| enemy.a_repere_joueur).count() as u32;
+            run.monde_suivant();
             Some(
-                SceneEnum::SceneWorld(
-                    SceneWorld::new(
+                SceneEnum::SceneAmelioration(
+                    SceneAmelioration::new(
                         Rc::clone(&self.input_service),
                         Rc::clone(&self.text_service),
                         Rc::clone(&self.sprite_service),
                         Rc::clone(&self.music_service),
-                        self.data.compteur_de_monde_genere + 1
+                        Rc::clone(&self.translation_service),
+                        run
                     )
                 )
             )
-        } else if self.player_collide_with_enemy() {
+        } else if self.data.player.sante.est_mort() {
+            if let Err(erreur) = SceneWorldData::delete_save() {
+                eprintln!("erreur lors de la suppression de la sauvegarde : {}", erreur);
+            }
             Some(
                 SceneEnum::SceneGameOver(
                     SceneGameOver::new(
@@ -108,7 +184,8 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
                         Rc::clone(&self.text_service),
                         Rc::clone(&self.sprite_service),
                         Rc::clone(&self.music_service),
-                        self.data.compteur_de_monde_genere
+                        Rc::clone(&self.translation_service),
+                        self.data.etat_de_la_partie()
                     )
                 )
             )
@@ -118,6 +195,41 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
 
     }
 
+    // arme active, munitions ou rechargement
+    fn ligne_arme(&self) -> String {
+        let traductions = self.translation_service.borrow();
+        let player = &self.data.player;
+        let arme = player.arme();
+
+        match player.rechargement {
+            Some(_) => traductions.tr_args("monde.rechargement", &[("arme", arme.arme.nom.clone())]),
+            None => traductions.tr_args(
+                "monde.arme",
+                &[
+                    ("index", (player.arme_active + 1).to_string()),
+                    ("arme", arme.arme.nom.clone()),
+                    ("chargeur", arme.chargeur.to_string()),
+                    ("reserve", arme.reserve.to_string())
+                ]
+            )
+        }
+    }
+
+    // le monde sans mise a jour (aussi affiche sous l'inventaire)
+    pub fn draw_monde(&mut self) {
+        self.draw_near_tilemaps().expect("erreur lors de l'affichage de la map");
+        if self.data.affiche_zones_apparition {
+            self.draw_zones_apparition().expect("erreur lors de l'affichage des zones d'apparition");
+        }
+        self.draw_vaisseau_a_trouver().expect("erreur lors de l'affichage du vaisseau");
+        self.draw_objets().expect("erreur lors de l'affichage des objets");
+        self.draw_player().expect("erreur lors de l'affichage du player");
+        self.draw_enemies().expect("erreur lors de l'affichage du player");
+        self.draw_projectiles().expect("erreur lors de l'affichage des projectiles");
+        self.draw_coup().expect("erreur lors de l'affichage du coup");
+        self.draw_cursor().expect("erreur lors de l'affichage du curseur");
+    }
+
     fn get_keys_pressed(&self) -> String {
         self
             .input_service
@@ -139,21 +251,41 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
         text_service: Rc<RefCell<TextService>>,
         sprite_service: Rc<RefCell<SpriteService>>,
         music_service: Rc<RefCell<MusicService>>,
-        compteur_de_monde_genere: u32
+        translation_service: Rc<RefCell<TranslationService>>,
+        run: RunState
+    ) -> Self {
+        Self::from_data(
+            key_manager,
+            text_service,
+            sprite_service,
+            music_service,
+            translation_service,
+            SceneWorldData::new(run)
+        )
+    }
+
+    pub fn from_data(
+        key_manager: Rc<RefCell<InputService>>,
+        text_service: Rc<RefCell<TextService>>,
+        sprite_service: Rc<RefCell<SpriteService>>,
+        music_service: Rc<RefCell<MusicService>>,
+        translation_service: Rc<RefCell<TranslationService>>,
+        data: SceneWorldData
     ) -> Self {
         Self {
             input_service: key_manager,
             text_service,
             sprite_service,
             music_service,
-            data: SceneWorldData::new(compteur_de_monde_genere)
+            translation_service,
+            data
         }
     }
 
     fn init_scene(&mut self) -> Result<(), String> {
         if !self.data.is_init {
             self.data.is_init = true;
-            self.music_service.borrow().play("hold-the-line", 20)
+            self.music_service.borrow_mut().play_playlist("monde")
         } else {
             Ok(())
         }
@@ -168,7 +300,7 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
             let mut col_body = self.data.player.collide_body.clone();
             col_body.position.y -= vitesse_temps;
 
-            if !col_body.is_collide_with_tilemap_hudge(&self.data.tilemap, vec![TileType::Mur]) {
+            if !col_body.is_collide_with_tilemap_hudge(&self.data.tilemap, self.data.tilemap.generateur.tuiles_bloquantes()) {
                 self.data.player.pos.y -= vitesse_temps;
                 self.data.player.collide_body.position.y -= vitesse_temps;
             }
@@ -178,7 +310,7 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
             let mut col_body = self.data.player.collide_body.clone();
             col_body.position.x += vitesse_temps;
 
-            if !col_body.is_collide_with_tilemap_hudge(&self.data.tilemap, vec![TileType::Mur]) {
+            if !col_body.is_collide_with_tilemap_hudge(&self.data.tilemap, self.data.tilemap.generateur.tuiles_bloquantes()) {
                 self.data.player.pos.x += vitesse_temps;
                 self.data.player.collide_body.position.x += vitesse_temps;
             }
@@ -188,7 +320,7 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
             let mut col_body = self.data.player.collide_body.clone();
             col_body.position.y += vitesse_temps;
 
-            if !col_body.is_collide_with_tilemap_hudge(&self.data.tilemap, vec![TileType::Mur]) {
+            if !col_body.is_collide_with_tilemap_hudge(&self.data.tilemap, self.data.tilemap.generateur.tuiles_bloquantes()) {
                 self.data.player.pos.y += vitesse_temps;
                 self.data.player.collide_body.position.y += vitesse_temps;
             }
@@ -198,17 +330,216 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
             let mut col_body = self.data.player.collide_body.clone();
             col_body.position.x -= vitesse_temps;
 
-            if !col_body.is_collide_with_tilemap_hudge(&self.data.tilemap, vec![TileType::Mur]) {
+            if !col_body.is_collide_with_tilemap_hudge(&self.data.tilemap, self.data.tilemap.generateur.tuiles_bloquantes()) {
                 self.data.player.pos.x -= vitesse_temps;
                 self.data.player.collide_body.position.x -= vitesse_temps;
             }
         }
 
+        // recul apres un coup
+        let recul = self.data.player.sante.update(dt);
+        let bloquantes = self.data.tilemap.generateur.tuiles_bloquantes();
+        self.data.player.collide_body.deplacer_si_libre(&recul, &self.data.tilemap, &bloquantes);
+        self.data.player.pos = self.data.player.collide_body.position.clone();
+
         Ok(())
     }
 
+    // fait arriver les vagues juste hors de la vue, sur une tuile libre d'un chunk charge
+    fn update_spawner(&mut self, dt: f32) {
+        let niveau = self.data.run.compteur_de_monde;
+        let taille_ecran = Vecteur2D::new(800f32, 600f32); // fixme utiliser un service window afin de recup les infos de la window
+        let nb_proches = self.data.enemies.iter()
+            .filter(|enemy| self.data.spawner.est_pres_de_la_vue(&enemy.collide_body.position, &self.data.camera, &taille_ecran))
+            .count();
+        let nombre = self.data.spawner.update(dt, niveau, nb_proches);
+        let bloquantes = self.data.tilemap.generateur.tuiles_bloquantes();
+
+        (0..nombre).for_each(|_| {
+            let enemy = (0..self.data.regles_apparition.essais).find_map(|_| {
+                let position = self.data.spawner.point_hors_ecran(&self.data.camera, &taille_ecran);
+                let r#type = self.data.tilemap.get_tile_from_position(&position)?.r#type.clone();
+
+                if bloquantes.contains(&r#type) || self.data.regles_apparition.poids(&r#type) <= 0.0 {
+                    return None;
+                }
+
+                let archetype = self.data.archetypes.tirer_pour_biome(&r#type, &mut rand::thread_rng())?;
+                let corps = CollideBody::basic(position.clone(), archetype.rayon);
+                if corps.is_collide_with_tilemap_hudge(&self.data.tilemap, bloquantes.clone()) {
+                    return None;
+                }
+
+                Some(Enemy::new(position, archetype))
+            });
+
+            if let Some(mut enemy) = enemy {
+                enemy.traquer(&self.data.player.pos);
+                self.data.enemies.push(enemy);
+            }
+        });
+    }
+
     fn update_enemies(&mut self, dt: f32) {
-        self.data.enemies.iter_mut().for_each(|e: &mut Enemy| e.update(dt, &self.data.tilemap, &self.data.player));
+        self.data.pathfinder.debut_frame(dt);
+        let evenements = self.data.enemies.iter_mut()
+            .filter_map(|e: &mut Enemy| e.update(dt, &self.data.tilemap, &self.data.player, &mut self.data.pathfinder))
+            .collect::<Vec<_>>();
+
+        evenements.into_iter().for_each(|evenement| match evenement {
+            EvenementEnnemi::Tir { projectile, son } => {
+                self.music_service.borrow_mut().play_sound(son.as_str(), 1, Some(projectile.position.clone()))
+                    .expect("erreur lors de la lecture du son de tir");
+                self.data.projectiles.push(projectile);
+            }
+        });
+    }
+
+    // changement d'arme : touches 1 a 9 ou molette
+    fn update_inventaire_armes(&mut self) {
+        let input_service = self.input_service.borrow();
+        let player = &mut self.data.player;
+
+        if let Some(index) = (0..player.armes.len().min(9)).find(|index| input_service.is_key_pressed((index + 1).to_string().as_str())) {
+            player.equiper(index);
+        }
+
+        let crans = input_service.mouse_wheel();
+        if crans != 0 {
+            player.arme_suivante(-crans);
+        }
+    }
+
+    // tir vers le curseur (clic gauche ou X) au rythme de l'arme, rechargement avec R ou chargeur vide
+    fn update_tir(&mut self, dt: f32) {
+        let player = &mut self.data.player;
+        player.recharge_arme -= dt;
+
+        if let Some(reste) = player.rechargement {
+            if reste - dt <= 0.0 {
+                player.arme_mut().recharger();
+                player.rechargement = None;
+            } else {
+                player.rechargement = Some(reste - dt);
+            }
+        }
+
+        let tire = self.input_service.borrow().is_key_mouse_pressed("left")
+            || self.input_service.borrow().is_key_pressed("X");
+        let recharge = self.input_service.borrow().is_key_pressed("R")
+            || (tire && player.arme().chargeur == 0);
+
+        if recharge && player.commencer_rechargement() {
+            self.music_service.borrow_mut().play_sound(player.arme().arme.son_rechargement.as_str(), 1, Some(player.pos.clone()))
+                .expect("erreur lors de la lecture du son de rechargement");
+        }
+
+        if tire && player.recharge_arme <= 0.0 && player.rechargement.is_none() && player.arme().chargeur > 0 {
+            let arme = player.arme().arme.clone();
+            player.recharge_arme = arme.delai_entre_tirs();
+            player.arme_mut().chargeur -= 1;
+
+            let multiplicateur = player.multiplicateur_degats;
+            self.data.projectiles.extend(
+                arme.tirer(&player.pos, &self.data.pos_curseur)
+                    .into_iter()
+                    .map(|mut projectile| {
+                        projectile.degats *= multiplicateur;
+                        projectile
+                    })
+            );
+            self.music_service.borrow_mut().play_sound(arme.son.as_str(), 1, Some(player.pos.clone()))
+                .expect("erreur lors de la lecture du son arme");
+        }
+    }
+
+    // coup au corps a corps vers le curseur (clic droit) : touche tous les ennemis dans l'arc
+    fn update_melee(&mut self, dt: f32) {
+        self.data.player.recharge_melee -= dt;
+        self.data.coup_en_cours = self.data.coup_en_cours.take()
+            .map(|(arc, reste)| (arc, reste - dt))
+            .filter(|(_, reste)| *reste > 0.0);
+
+        if !self.input_service.borrow().is_key_mouse_pressed("right") || self.data.player.recharge_melee > 0.0 {
+            return;
+        }
+
+        let player = &mut self.data.player;
+        let melee = player.melee.clone();
+        let direction = Vecteur2D::<f32>::from_points(&player.pos, &self.data.pos_curseur);
+        let arc = match ArcDeCercle::new(player.pos.clone(), &direction, melee.ouverture.to_radians(), melee.portee) {
+            Some(arc) => arc,
+            None => return
+        };
+        player.recharge_melee = melee.recharge;
+        let montant = melee.degats * player.multiplicateur_degats;
+
+        self.data.enemies.iter_mut()
+            .filter(|e| e.est_visible() && !e.sante.est_mort())
+            .filter(|e| arc.touche_cercle(&e.collide_body.position, e.archetype.rayon))
+            .for_each(|e| {
+                e.sante.subir(&Degats {
+                    montant,
+                    direction: Vecteur2D::<f32>::from_points(&arc.centre, &e.collide_body.position)
+                        .unitaire()
+                        .unwrap_or(Vecteur2D::new(0.0, 0.0)),
+                    recul: melee.recul
+                });
+            });
+
+        self.data.coup_en_cours = Some((arc, melee.duree_effet));
+    }
+
+    // deplace les projectiles, retire ceux qui touchent un mur ou un ennemi
+    fn update_projectiles(&mut self, dt: f32) {
+        let tilemap = &self.data.tilemap;
+        let enemies = &mut self.data.enemies;
+
+        self.data.projectiles.retain_mut(|projectile| {
+            if !projectile.avancer(dt, tilemap) {
+                return false;
+            }
+            if !projectile.du_joueur {
+                return true;
+            }
+
+            match enemies.iter_mut().find(|e| {
+                e.est_visible() && !e.sante.est_mort() && projectile.touche(&e.collide_body.position, e.archetype.rayon)
+            }) {
+                Some(enemy) => {
+                    enemy.sante.subir(&projectile.degats());
+                    false
+                }
+                None => true
+            }
+        });
+    }
+
+    // retire les ennemis tues par le joueur (tir ou corps a corps), qui peuvent laisser un objet
+    fn retirer_ennemis_morts(&mut self) {
+        let (morts, vivants) = self.data.enemies
+            .drain(..)
+            .partition::<Vec<Enemy>, _>(|e| e.sante.est_mort());
+        self.data.enemies = vivants;
+
+        let mut rng = rand::thread_rng();
+        self.data.run.ennemis_tues += morts.len() as u32;
+        self.data.run.score += morts.iter().map(|e| e.archetype.points).sum::<u32>();
+        morts.iter().for_each(|e| {
+            if let Some(definition) = self.data.butins.tirer(e.archetype.butin.as_str(), &mut rng) {
+                self.data.objets.push(Objet::new(e.collide_body.position.clone(), definition));
+            }
+        });
+    }
+
+    // ramasse les objets a portee du joueur
+    fn update_objets(&mut self) {
+        let player = &mut self.data.player;
+        let monnaie = &mut self.data.run.monnaie;
+
+        self.data.objets.retain(|objet| {
+            !(objet.peut_etre_ramasse(&player.pos, player.bonus_ramassage) && objet.appliquer(player, monnaie))
+        });
     }
 
     fn update_curseur(&mut self) {
@@ -245,13 +576,30 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
         }
     }
 
-    fn player_collide_with_enemy(&self) -> bool {
-        self.data.enemies.iter()
-            .find(|e| {
-                Vecteur2D::<f32>::from_points(&e.collide_body.position, &self.data.player.pos)
-                    .norme() < 32.0
+    // coups recus par le joueur : contact des ennemis et projectiles ennemis (consommes meme si le joueur est invulnerable)
+    fn update_degats_joueur(&mut self) {
+        let pos_joueur = self.data.player.pos.clone();
+
+        let mut degats = self.data.enemies.iter()
+            .filter(|e| e.est_visible())
+            .filter(|e| {
+                Vecteur2D::<f32>::from_points(&e.collide_body.position, &pos_joueur)
+                    .norme() < 16.0 + e.archetype.rayon
             })
-            .is_some()
+            .map(|e| e.degats_contact(&pos_joueur))
+            .collect::<Vec<Degats>>();
+
+        self.data.projectiles.retain(|projectile| {
+            let touche = !projectile.du_joueur && projectile.touche(&pos_joueur, 16.0);
+            if touche {
+                degats.push(projectile.degats());
+            }
+            !touche
+        });
+
+        degats.iter().for_each(|coup| {
+            self.data.player.sante.subir(coup);
+        });
     }
 
     fn update_camera(&mut self) {
@@ -264,7 +612,31 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
         );
     }
 
+    fn update_listener(&mut self) {
+        self.music_service.borrow_mut().set_listener(self.data.player.pos.clone());
+    }
+
+    // playlist du biome sous le joueur et intensite selon les ennemis en chasse
+    fn update_music(&mut self) -> Result<(), String> {
+        let nb_ennemis_max = 5.0;
+        let nb_ennemis_proches = self.data.enemies.iter()
+            .filter(|enemy| enemy.est_en_chasse())
+            .count();
+
+        let mut music_service = self.music_service.borrow_mut();
+        music_service.set_intensity(nb_ennemis_proches as f32 / nb_ennemis_max);
+
+        match self.data.tilemap.get_tile_from_position(&self.data.player.pos) {
+            Some(tile) => music_service.play_playlist(format!("monde.{}", tile.r#type.code()).as_str()),
+            None => Ok(())
+        }
+    }
+
     fn draw_player(&mut self) -> Result<(), String> {
+        if !self.data.player.sante.visible() {
+            return Ok(());
+        }
+
         self.sprite_service.borrow_mut().draw_sprite(
             "poulet",
             Vecteur2D::new(
@@ -280,6 +652,7 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
             .clone()
             .iter()
             .map(|e| e.clone())
+            .filter(|e| e.est_visible())
             .for_each(|e| {
                 self.draw_enemy(&e).expect("erreur affichage")
             });
@@ -288,16 +661,82 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
     }
 
     fn draw_enemy(&mut self, enemy: &Enemy) -> Result<(), String> {
+        let archetype = &enemy.archetype;
+        let demi_taille = archetype.taille_affichage as f32 / 2.0;
+
         self.sprite_service.borrow_mut().draw_sprite(
-            "croco",
+            archetype.sprite.as_str(),
             Vecteur2D::new(
-                (enemy.collide_body.position.x - self.data.camera.x - 16f32) as i32,
-                (enemy.collide_body.position.y - self.data.camera.y - 16f32) as i32
+                (enemy.collide_body.position.x - self.data.camera.x - demi_taille) as i32,
+                (enemy.collide_body.position.y - self.data.camera.y - demi_taille) as i32
             )
-            ,Some(Vecteur2D::new(128, 128)), Some(Vecteur2D::new(32, 32))
+            ,Some(archetype.taille_sprite.clone()), Some(Vecteur2D::new(archetype.taille_affichage, archetype.taille_affichage))
         )
     }
 
+    fn draw_barre_de_vie(&mut self) -> Result<(), String> {
+        let position = Vecteur2D::new(240, 36);
+        let largeur = 130u32;
+        let hauteur = 10u32;
+        let remplie = (largeur as f32 * self.data.player.sante.ratio()) as u32;
+
+        let mut sprite_service = self.sprite_service.borrow_mut();
+        sprite_service.draw_rect(position.clone(), Vecteur2D::new(largeur, hauteur), Color::rgba(60, 0, 0, 200))?;
+        sprite_service.draw_rect(position, Vecteur2D::new(remplie, hauteur), Color::rgb(220, 30, 30))
+    }
+
+    // trainee de l'arc, de plus en plus transparente
+    fn draw_coup(&mut self) -> Result<(), String> {
+        let (arc, reste) = match &self.data.coup_en_cours {
+            Some(coup) => coup.clone(),
+            None => return Ok(())
+        };
+        let opacite = (255.0 * reste / self.data.player.melee.duree_effet).clamp(0.0, 255.0) as u8;
+
+        arc.points_du_bord(16).iter().try_for_each(|point| {
+            self.sprite_service.borrow_mut().draw_rect(
+                Vecteur2D::new(
+                    (point.x - self.data.camera.x - 3.0) as i32,
+                    (point.y - self.data.camera.y - 3.0) as i32
+                ),
+                Vecteur2D::new(6, 6),
+                Color::rgba(255, 255, 255, opacite)
+            )
+        })
+    }
+
+    fn draw_projectiles(&mut self) -> Result<(), String> {
+        let taille = (RAYON_PROJECTILE * 2.0) as u32;
+
+        self.data.projectiles.iter().try_for_each(|projectile| {
+            self.sprite_service.borrow_mut().draw_rect(
+                Vecteur2D::new(
+                    (projectile.position.x - self.data.camera.x - RAYON_PROJECTILE) as i32,
+                    (projectile.position.y - self.data.camera.y - RAYON_PROJECTILE) as i32
+                ),
+                Vecteur2D::new(taille, taille),
+                if projectile.du_joueur { Color::rgb(255, 220, 0) } else { Color::rgb(255, 40, 40) }
+            )
+        })
+    }
+
+    fn draw_objets(&mut self) -> Result<(), String> {
+        self.data.objets.iter().try_for_each(|objet| {
+            let definition = &objet.definition;
+            let demi_taille = definition.taille_affichage as f32 / 2.0;
+
+            self.sprite_service.borrow_mut().draw_sprite(
+                definition.sprite.as_str(),
+                Vecteur2D::new(
+                    (objet.collide_body.position.x - self.data.camera.x - demi_taille) as i32,
+                    (objet.collide_body.position.y - self.data.camera.y - demi_taille) as i32
+                ),
+                Some(definition.taille_sprite.clone()),
+                Some(Vecteur2D::new(definition.taille_affichage, definition.taille_affichage))
+            )
+        })
+    }
+
     fn draw_vaisseau_a_trouver(&mut self) -> Result<(), String> {
         self.sprite_service.borrow_mut().draw_sprite(
             "porte",
@@ -321,41 +760,15 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
     }
 
 
+    // chunks couverts par la camera, du coin haut gauche de l'ecran au coin bas droit
     fn draw_near_tilemaps(&self) -> Result<(), String> {
+        let taille_ecran = Vecteur2D::new(800f32, 600f32); // fixme utiliser un service window afin de recup les infos de la window
+        let debut = self.data.tilemap.get_tilemap_index_from_position(&self.data.camera);
+        let fin = self.data.tilemap.get_tilemap_index_from_position(&(self.data.camera.clone() + taille_ecran));
 
-        let pos_tilemap = self.data.tilemap.get_tilemap_index_from_position(&self.data.player.pos);
-
-        let max_x = self.data.tilemap.nb_tilemap_column as i32 - 1;
-        let max_y = self.data.tilemap.nb_tilemap_line as i32 - 1;
-
-        let x = pos_tilemap.x as i32;
-        let y = pos_tilemap.y as i32;
-
-        let pos = [
-            Vecteur2D::new(x - 1, y - 1), // haut - gauche
-            Vecteur2D::new(x, y - 1), // haut
-            Vecteur2D::new(x + 1, y - 1), // haut - droit
-
-            Vecteur2D::new(x - 1, y), // gauche
-            Vecteur2D::new(x, y), // centre
-            Vecteur2D::new(x + 1, y), // droit
-
-            Vecteur2D::new(x - 1, y + 1), // bas - gauche
-            Vecteur2D::new(x, y + 1), // bas
-            Vecteur2D::new(x + 1, y + 1), // bas - droit
-        ];
-
-        pos
-            .iter()
-            .filter(|pos| {
-                pos.x >= 0 && pos.y >= 0 && pos.x <= max_x && pos.y <= max_y
-            })
-            .map(|index| {
-                self.data.tilemap
-                    .get_tilemap_from_index(
-                        &Vecteur2D::new(index.x as u32, index.y as u32)
-                    )
-            })
+        (debut.y..=fin.y)
+            .flat_map(|y| (debut.x..=fin.x).map(move |x| Vecteur2D::new(x, y)))
+            .filter_map(|index| self.data.tilemap.get_tilemap_from_index(&index))
             .for_each(|tilemap| {
                 self.draw_one_tilemap(tilemap)
                     .expect("erreur lors de l'affichage de la tilemap");
@@ -389,7 +802,7 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
                             TileType::Sand => "tile_sand",
                             TileType::Snow => "tile_snow",
                             TileType::Goo => "tile_goo",
-                            TileType::Wood => "tile_wood",
+                            TileType::Wood | TileType::Porte => "tile_wood",
                             _ => "tile_herbe"
                         };
 
@@ -401,6 +814,18 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
                             )
                             , Some(Vecteur2D::new(64, 74)), Some(Vecteur2D::new(32, 51))
                         ).expect("erreur de lors de la 'affiche de la tuile");
+
+                        // porte : plancher et sprite de porte par dessus
+                        if current.r#type == TileType::Porte {
+                            self.sprite_service.borrow_mut().draw_sprite(
+                                "porte",
+                                Vecteur2D::new(
+                                    current.pos.x as i32 * 32 - self.data.camera.x as i32,
+                                    current.pos.y as i32 * 32 - self.data.camera.y as i32
+                                )
+                                , Some(Vecteur2D::new(64, 64)), Some(Vecteur2D::new(32, 32))
+                            ).expect("erreur de lors de la 'affiche de la porte");
+                        }
                     });
             });
 
@@ -415,9 +840,61 @@ impl<SpriteService, TextService, InputService, MusicService> SceneWorld<SpriteSe
         point_x > 0 - margin && point_x < window_width && point_y > 0 - margin && point_y < window_height
     }
 
-    fn test_play_sound(&self) {
-        if self.input_service.borrow().is_key_pressed("X") {
-            self.music_service.borrow().play_sound("arme", 1).expect("erreur lors de la lecture du son arme");
+    // touche I, sur l'appui seulement
+    fn ouvre_inventaire(&mut self) -> bool {
+        let est_pressee = self.input_service.borrow().is_key_pressed("I");
+        let ouvre = est_pressee && !self.data.touche_inventaire_pressee;
+
+        self.data.touche_inventaire_pressee = est_pressee;
+        ouvre
+    }
+
+    // touche Echap, sur l'appui seulement
+    fn quitte_vers_menu(&mut self) -> bool {
+        let est_pressee = self.input_service.borrow().is_key_pressed("Escape");
+        let quitte = est_pressee && !self.data.touche_echap_pressee;
+
+        self.data.touche_echap_pressee = est_pressee;
+        quitte
+    }
+
+    fn update_debug(&mut self) {
+        let est_pressee = self.input_service.borrow().is_key_pressed("F3");
+
+        if est_pressee && !self.data.touche_debug_pressee {
+            self.data.affiche_zones_apparition = !self.data.affiche_zones_apparition;
         }
+
+        self.data.touche_debug_pressee = est_pressee;
+    }
+
+    // tuiles a l'ecran : vert si un ennemi peut y apparaitre, rouge sinon
+    // (l'espacement entre ennemis n'est pas represente)
+    fn draw_zones_apparition(&mut self) -> Result<(), String> {
+        let tile_size = self.data.tilemap.tile_size as i32;
+        let debut = self.data.tilemap.get_tile_index_from_position(&self.data.camera);
+        let (zone_min, zone_max) = &self.data.zone_apparition;
+        let bloquantes = self.data.tilemap.generateur.tuiles_bloquantes();
+
+        (debut.y..=debut.y + 600 / tile_size).try_for_each(|y| {
+            (debut.x..=debut.x + 800 / tile_size).try_for_each(|x| {
+                let index = Vecteur2D::new(x, y);
+                let valide = x >= zone_min.x && x < zone_max.x && y >= zone_min.y && y < zone_max.y
+                    && self.data.tilemap.get_tile_at(&index)
+                        .map(|tile| {
+                            self.data.regles_apparition.position_valide(&index, &tile.r#type, &bloquantes, &self.data.point_depart)
+                        })
+                        .unwrap_or(false);
+
+                self.sprite_service.borrow_mut().draw_rect(
+                    Vecteur2D::new(
+                        x * tile_size - self.data.camera.x as i32,
+                        y * tile_size - self.data.camera.y as i32
+                    ),
+                    Vecteur2D::new(tile_size as u32, tile_size as u32),
+                    if valide { Color::rgba(0, 255, 0, 70) } else { Color::rgba(255, 0, 0, 50) }
+                )
+            })
+        })
     }
 }
\ No newline at end of file
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use rand::Rng;
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::physics::collide_body::CollideBody;
+use crate::core::scene::scene_world::inventory::DefinitionItem;
+use crate::core::scene::scene_world::player::Player;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const LOOT_PATH: &str = "assets/data/loot.ini";
+
+#[derive(Clone)]
+pub enum EffetObjet {
+    Soin(f32), // points de vie rendus
+    Munitions(u32), // chargeurs ajoutes a la reserve de l'arme active
+    Monnaie(u32),
+    Item(Rc<DefinitionItem>) // range dans l'inventaire
+}
+
+impl EffetObjet {
+    // false si l'effet ne sert a rien pour l'instant (vie pleine, reserve pleine, inventaire plein)
+    pub fn appliquer(&self, joueur: &mut Player, monnaie: &mut u32) -> bool {
+        match self {
+            EffetObjet::Soin(points) => {
+                if joueur.sante.points >= joueur.sante.max {
+                    return false;
+                }
+                joueur.sante.soigner(*points);
+                true
+            }
+            EffetObjet::Munitions(chargeurs) => {
+                let equipee = joueur.arme_mut();
+                if equipee.reserve >= equipee.arme.munitions_max {
+                    return false;
+                }
+                equipee.reserve = (equipee.reserve + chargeurs * equipee.arme.taille_chargeur).min(equipee.arme.munitions_max);
+                true
+            }
+            EffetObjet::Monnaie(valeur) => {
+                *monnaie += valeur;
+                true
+            }
+            EffetObjet::Item(definition) => joueur.inventaire.ajouter(definition, 1) == 0
+        }
+    }
+}
+
+// objet ramassable defini dans loot.ini
+pub struct DefinitionObjet {
+    pub nom: String,
+    pub sprite: String,
+    pub taille_sprite: Vecteur2D<u32>,
+    pub taille_affichage: u32,
+    pub rayon_ramassage: f32,
+    pub effet: EffetObjet
+}
+
+impl DefinitionObjet {
+    fn from_section(path: &str, section: &IniSection, items: &HashMap<String, Rc<DefinitionItem>>) -> Result<Self, String> {
+        let nom = section.nom["objet.".len()..].to_string();
+
+        let effet = match section.get("effet").unwrap_or("") {
+            "soin" => EffetObjet::Soin(section.get_or("valeur", 1.0)),
+            "munitions" => EffetObjet::Munitions(section.get_or("valeur", 1)),
+            "monnaie" => EffetObjet::Monnaie(section.get_or("valeur", 1)),
+            "item" => {
+                let item = section.get("item").unwrap_or("");
+                EffetObjet::Item(
+                    items.get(item)
+                        .cloned()
+                        .ok_or(format!("{} : item inconnu '{}' pour l'objet '{}'", path, item, nom))?
+                )
+            }
+            autre => return Err(format!("{} : effet inconnu '{}' pour l'objet '{}'", path, autre, nom))
+        };
+
+        let taille_sprite = section.get_or("taille_sprite", 32);
+
+        Ok(Self {
+            sprite: section.get_or("sprite", "smiley".to_string()),
+            taille_sprite: Vecteur2D::new(taille_sprite, taille_sprite),
+            taille_affichage: section.get_or("taille_affichage", 16),
+            rayon_ramassage: section.get_or("rayon_ramassage", 24.0),
+            effet,
+            nom
+        })
+    }
+}
+
+// objet pose dans le monde
+#[derive(Clone)]
+pub struct Objet {
+    pub definition: Rc<DefinitionObjet>,
+    pub collide_body: CollideBody
+}
+
+impl Objet {
+    pub fn new(position: Vecteur2D<f32>, definition: Rc<DefinitionObjet>) -> Self {
+        Self {
+            definition,
+            collide_body: CollideBody::new(position, vec![Vecteur2D::new(0f32, 0f32)])
+        }
+    }
+
+    pub fn peut_etre_ramasse(&self, position_joueur: &Vecteur2D<f32>, bonus_ramassage: f32) -> bool {
+        self.collide_body.is_collide_with_object(position_joueur, self.definition.rayon_ramassage + bonus_ramassage)
+    }
+
+    // un objet sans effet pour l'instant reste au sol
+    pub fn appliquer(&self, joueur: &mut Player, monnaie: &mut u32) -> bool {
+        self.definition.effet.appliquer(joueur, monnaie)
+    }
+}
+
+type TableDeButin = Vec<(Option<Rc<DefinitionObjet>>, f32)>;
+
+// objets et tables de butin (cf loot.ini)
+pub struct Butins {
+    pub objets_par_niveau: f32, // objets poses a la generation du monde
+    objets: HashMap<String, Rc<DefinitionObjet>>,
+    tables: HashMap<String, TableDeButin> // objet (None = rien) et poids
+}
+
+impl Butins {
+    pub fn load(path: &str, items: &HashMap<String, Rc<DefinitionItem>>) -> Result<Self, String> {
+        let fichier = IniFile::load(path)?;
+
+        let objets = fichier.sections_prefixees("objet.")
+            .into_iter()
+            .map(|section| DefinitionObjet::from_section(path, section, items).map(|objet| (objet.nom.clone(), Rc::new(objet))))
+            .collect::<Result<HashMap<_, _>, String>>()?;
+
+        let tables = fichier.sections_prefixees("table.")
+            .into_iter()
+            .map(|section| {
+                let nom = section.nom["table.".len()..].to_string();
+                let entrees = section.entrees
+                    .iter()
+                    .map(|(objet, poids)| {
+                        let poids = poids.parse::<f32>()
+                            .map_err(|_| format!("{} : poids invalide '{}' dans la table '{}'", path, poids, nom))?;
+                        match objet.as_str() {
+                            "rien" => Ok((None, poids)),
+                            objet => objets.get(objet)
+                                .map(|definition| (Some(Rc::clone(definition)), poids))
+                                .ok_or(format!("{} : objet inconnu '{}' dans la table '{}'", path, objet, nom))
+                        }
+                    })
+                    .collect::<Result<TableDeButin, String>>()?;
+                Ok((nom, entrees))
+            })
+            .collect::<Result<HashMap<_, _>, String>>()?;
+
+        let objets_par_niveau = fichier.section("placement")
+            .map(|section| section.get_or("objets_par_niveau", 6.0))
+            .unwrap_or(6.0);
+
+        Ok(Self { objets_par_niveau, objets, tables })
+    }
+
+    pub fn get(&self, nom: &str) -> Option<Rc<DefinitionObjet>> {
+        self.objets.get(nom).cloned()
+    }
+
+    // tirage pondere dans une table, None si la table n'existe pas ou si le tirage tombe sur "rien"
+    pub fn tirer<R: Rng + ?Sized>(&self, table: &str, rng: &mut R) -> Option<Rc<DefinitionObjet>> {
+        let entrees = self.tables.get(table)?;
+        let total = entrees.iter().map(|(_, poids)| poids).sum::<f32>();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut tirage = rng.gen_range(0.0..total);
+        entrees.iter()
+            .find(|(_, poids)| {
+                tirage -= poids;
+                tirage < 0.0
+            })
+            .or(entrees.last())
+            .and_then(|(objet, _)| objet.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    const NB_TIRAGES: usize = 4000;
+
+    fn objet(nom: &str) -> Rc<DefinitionObjet> {
+        Rc::new(DefinitionObjet {
+            nom: nom.to_string(),
+            sprite: "smiley".to_string(),
+            taille_sprite: Vecteur2D::new(32, 32),
+            taille_affichage: 16,
+            rayon_ramassage: 24.0,
+            effet: EffetObjet::Monnaie(1)
+        })
+    }
+
+    fn butins(tables: &[(&str, TableDeButin)]) -> Butins {
+        Butins {
+            objets_par_niveau: 0.0,
+            objets: HashMap::new(),
+            tables: tables.iter().map(|(nom, table)| (nom.to_string(), table.clone())).collect()
+        }
+    }
+
+    // nombre de tirages par nom d'objet, "rien" quand le tirage ne donne rien
+    fn frequences(butins: &Butins, table: &str, graine: u64) -> HashMap<String, usize> {
+        let mut rng = StdRng::seed_from_u64(graine);
+        let mut frequences = HashMap::new();
+
+        (0..NB_TIRAGES).for_each(|_| {
+            let nom = butins.tirer(table, &mut rng).map(|objet| objet.nom.clone()).unwrap_or("rien".to_string());
+            *frequences.entry(nom).or_insert(0) += 1;
+        });
+        frequences
+    }
+
+    #[test]
+    fn tirage_proportionnel_aux_poids() {
+        let butins = butins(&[("table", vec![(Some(objet("a")), 3.0), (Some(objet("b")), 1.0), (None, 4.0)])]);
+
+        (0..5).for_each(|graine| {
+            let frequences = frequences(&butins, "table", graine);
+            let part = |nom: &str| *frequences.get(nom).unwrap_or(&0) as f32 / NB_TIRAGES as f32;
+
+            assert!((part("a") - 3.0 / 8.0).abs() < 0.03, "graine {} : a tire {}", graine, part("a"));
+            assert!((part("b") - 1.0 / 8.0).abs() < 0.03, "graine {} : b tire {}", graine, part("b"));
+            assert!((part("rien") - 4.0 / 8.0).abs() < 0.03, "graine {} : rien tire {}", graine, part("rien"));
+        });
+    }
+
+    #[test]
+    fn entree_de_poids_nul_jamais_tiree() {
+        let butins = butins(&[("table", vec![(Some(objet("a")), 0.0), (Some(objet("b")), 1.0), (Some(objet("c")), 0.0)])]);
+
+        (0..5).for_each(|graine| {
+            assert_eq!(frequences(&butins, "table", graine).get("b"), Some(&NB_TIRAGES));
+        });
+    }
+
+    #[test]
+    fn table_vide_nulle_ou_inconnue_ne_donne_rien() {
+        let butins = butins(&[
+            ("vide", vec![]),
+            ("nulle", vec![(Some(objet("a")), 0.0)]),
+            ("rien", vec![(None, 1.0)])
+        ]);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        ["vide", "nulle", "rien", "inconnue"].iter().for_each(|table| {
+            assert!((0..100).all(|_| butins.tirer(table, &mut rng).is_none()), "table {}", table);
+        });
+    }
+}
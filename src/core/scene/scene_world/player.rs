@@ -1,19 +1,212 @@
+use std::rc::Rc;
+
+use crate::core::data::ini::{IniFile, IniSection};
 use crate::core::physics::collide_body::CollideBody;
+use crate::core::scene::scene_world::health::Sante;
+use crate::core::scene::scene_world::inventory::{BonusEquipement, Inventaire, UsageItem};
+use crate::core::scene::scene_world::melee::AttaqueMelee;
+use crate::core::scene::scene_world::run_state::RunState;
+use crate::core::scene::scene_world::upgrade::EffetAmelioration;
+use crate::core::scene::scene_world::weapon::{Arme, ArmeEquipee};
 use crate::core::sdd::vecteur2d::Vecteur2D;
 
+pub const PLAYER_PATH: &str = "assets/data/player.ini";
+
+// les ennemis gardent au moins 20% de leur distance de vue, meme avec toutes les ameliorations de discretion
+const DISCRETION_MAX: f32 = 0.8;
+
+// caracteristiques de depart du joueur (cf player.ini)
+#[derive(Clone)]
+pub struct StatsJoueur {
+    pub points_de_vie: f32,
+    pub vitesse: f32,
+    pub duree_invulnerabilite: f32,
+    pub capacite_inventaire: usize
+}
+
+impl StatsJoueur {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let fichier = IniFile::load(path)?;
+        let defaut = IniSection::new("joueur");
+        let joueur = fichier.section("joueur").unwrap_or(&defaut);
+
+        Ok(Self {
+            points_de_vie: joueur.get_or("points_de_vie", 5.0),
+            vitesse: joueur.get_or("vitesse", 450.0),
+            duree_invulnerabilite: joueur.get_or("duree_invulnerabilite", 1.0),
+            capacite_inventaire: joueur.get_or("capacite_inventaire", 12)
+        })
+    }
+}
+
 pub struct Player {
     pub pos: Vecteur2D<f32>,
     pub collide_body: CollideBody,
-    pub vitesse: f32
+    pub vitesse: f32,
+    pub sante: Sante,
+    pub inventaire: Inventaire,
+    pub armes: Vec<ArmeEquipee>,
+    pub arme_active: usize,
+    pub recharge_arme: f32, // secondes avant de pouvoir tirer de nouveau
+    pub rechargement: Option<f32>, // temps restant si un chargeur est en train d'etre rempli
+    pub melee: AttaqueMelee,
+    pub recharge_melee: f32,
+
+    // ameliorations choisies entre les mondes
+    pub bonus_ramassage: f32, // pixels ajoutes au rayon de ramassage
+    pub multiplicateur_degats: f32,
+    pub discretion: f32 // part retiree a la distance de vue des ennemis
 }
 
 impl Player {
-    pub fn new() -> Self {
-        let position_de_depart = Vecteur2D::new(64f32, 64f32);
+    pub fn new(position_de_depart: Vecteur2D<f32>, armes: Vec<Arme>, melee: AttaqueMelee, stats: &StatsJoueur) -> Self {
         Self {
             pos: position_de_depart.clone(),
             collide_body: CollideBody::basic(position_de_depart, 16f32),
-            vitesse: 450f32
+            vitesse: stats.vitesse,
+            sante: Sante::new(stats.points_de_vie, stats.duree_invulnerabilite),
+            inventaire: Inventaire::new(stats.capacite_inventaire),
+            armes: armes.into_iter().map(ArmeEquipee::new).collect(),
+            arme_active: 0,
+            recharge_arme: 0.0,
+            rechargement: None,
+            melee,
+            recharge_melee: 0.0,
+            bonus_ramassage: 0.0,
+            multiplicateur_degats: 1.0,
+            discretion: 0.0
+        }
+    }
+
+    pub fn arme(&self) -> &ArmeEquipee {
+        &self.armes[self.arme_active]
+    }
+
+    pub fn arme_mut(&mut self) -> &mut ArmeEquipee {
+        &mut self.armes[self.arme_active]
+    }
+
+    // changer d'arme annule le rechargement en cours
+    pub fn equiper(&mut self, index: usize) {
+        if index < self.armes.len() && index != self.arme_active {
+            self.arme_active = index;
+            self.rechargement = None;
         }
     }
-}
\ No newline at end of file
+
+    // decalage circulaire dans l'inventaire (molette)
+    pub fn arme_suivante(&mut self, decalage: i32) {
+        let nombre = self.armes.len() as i32;
+        self.equiper((self.arme_active as i32 + decalage).rem_euclid(nombre) as usize);
+    }
+
+    // utilise l'item de la case `index` : un consommable est applique puis retire, un equipement est porte
+    pub fn utiliser_item(&mut self, index: usize, monnaie: &mut u32) -> bool {
+        let definition = match self.inventaire.piles.get(index) {
+            Some(pile) => Rc::clone(&pile.definition),
+            None => return false
+        };
+
+        match &definition.usage {
+            UsageItem::Consommable(effet) => {
+                if !effet.appliquer(self, monnaie) {
+                    return false;
+                }
+                self.inventaire.retirer(index, 1);
+                true
+            }
+            UsageItem::Equipement(bonus) => {
+                // l'equipement deja porte a cet emplacement revient dans l'inventaire
+                if let Some(porte) = self.inventaire.equipe_sur(&bonus.emplacement) {
+                    let ancien = Rc::clone(&self.inventaire.equipes[porte]);
+
+                    // seul dans sa pile, l'item equipe libere sa case pour l'ancien
+                    let libere_sa_case = self.inventaire.piles[index].quantite == 1;
+                    if libere_sa_case {
+                        self.inventaire.retirer(index, 1);
+                    }
+                    // sinon l'ancien doit trouver une pile ou une case libre, rien n'a encore change
+                    if self.inventaire.ajouter(&ancien, 1) > 0 {
+                        return false;
+                    }
+                    if !libere_sa_case {
+                        self.inventaire.retirer(index, 1);
+                    }
+
+                    self.inventaire.equipes.remove(porte);
+                    self.appliquer_bonus(&ancien.usage, -1.0);
+                } else {
+                    self.inventaire.retirer(index, 1);
+                }
+
+                self.appliquer_bonus(&definition.usage, 1.0);
+                self.inventaire.equipes.push(definition);
+                true
+            }
+        }
+    }
+
+    // retire l'equipement porte (index dans inventaire.equipes), false si l'inventaire est plein
+    pub fn desequiper(&mut self, index: usize) -> bool {
+        let definition = match self.inventaire.equipes.get(index) {
+            Some(definition) => Rc::clone(definition),
+            None => return false
+        };
+        if self.inventaire.ajouter(&definition, 1) > 0 {
+            return false;
+        }
+
+        self.inventaire.equipes.remove(index);
+        self.appliquer_bonus(&definition.usage, -1.0);
+        true
+    }
+
+    // inventaire rapporte d'un monde precedent : les bonus des equipements portes sont appliques au nouveau joueur
+    pub fn reprendre_inventaire(&mut self, inventaire: Inventaire) {
+        inventaire.equipes.iter().for_each(|definition| self.appliquer_bonus(&definition.usage, 1.0));
+        self.inventaire = inventaire;
+    }
+
+    // etat rapporte du monde precedent, rien a reprendre en debut de partie
+    pub fn reprendre_partie(&mut self, run: &RunState) {
+        if let Some(inventaire) = &run.inventaire {
+            self.reprendre_inventaire(inventaire.clone());
+        }
+        // apres les bonus d'equipement, qui changent le maximum
+        if let Some(sante) = run.sante {
+            self.sante.points = sante.min(self.sante.max);
+        }
+        if let Some((armes, active)) = &run.armes {
+            if !armes.is_empty() {
+                self.armes = armes.clone();
+                self.arme_active = (*active).min(armes.len() - 1);
+            }
+        }
+    }
+
+    pub fn appliquer_amelioration(&mut self, effet: &EffetAmelioration) {
+        match effet {
+            EffetAmelioration::Vitesse(valeur) => self.vitesse += valeur,
+            EffetAmelioration::Ramassage(valeur) => self.bonus_ramassage += valeur,
+            EffetAmelioration::Degats(valeur) => self.multiplicateur_degats += valeur,
+            EffetAmelioration::Discretion(valeur) => self.discretion = (self.discretion + valeur).min(DISCRETION_MAX)
+        }
+    }
+
+    fn appliquer_bonus(&mut self, usage: &UsageItem, signe: f32) {
+        if let UsageItem::Equipement(BonusEquipement { vitesse, points_de_vie, .. }) = usage {
+            self.vitesse += vitesse * signe;
+            self.sante.max += points_de_vie * signe;
+            self.sante.points = (self.sante.points + points_de_vie * signe).clamp(1.0, self.sante.max.max(1.0));
+        }
+    }
+
+    // false si le chargeur est deja plein ou la reserve vide
+    pub fn commencer_rechargement(&mut self) -> bool {
+        if self.rechargement.is_some() || !self.arme().peut_recharger() {
+            return false;
+        }
+        self.rechargement = Some(self.arme().arme.duree_rechargement);
+        true
+    }
+}
@@ -0,0 +1,49 @@
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::elements::tilemap::TileMapHudge;
+use crate::core::physics::raycast::raycast;
+use crate::core::scene::scene_world::health::Degats;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const RAYON_PROJECTILE: f32 = 3.0;
+
+#[derive(Clone)]
+pub struct Projectile {
+    pub position: Vecteur2D<f32>,
+    pub vitesse: Vecteur2D<f32>, // pixels par seconde
+    pub degats: f32,
+    pub recul: f32, // vitesse de recul donnee a la cible
+    pub reste: f32, // duree de vie restante en secondes
+    pub du_joueur: bool // tire par le joueur (touche les ennemis) ou par un ennemi (touche le joueur)
+}
+
+impl Projectile {
+    pub fn new(position: Vecteur2D<f32>, vitesse: Vecteur2D<f32>, degats: f32, recul: f32, duree_vie: f32, du_joueur: bool) -> Self {
+        Self { position, vitesse, degats, recul, reste: duree_vie, du_joueur }
+    }
+
+    // avance d'une frame, false si le projectile s'est eteint ou a touche un mur
+    // (le segment parcouru est teste en entier pour ne pas traverser les murs a grande vitesse)
+    pub fn avancer(&mut self, dt: f32, tilemap: &TileMapHudge) -> bool {
+        self.reste -= dt;
+
+        let arrivee = self.position.clone() + Vecteur2D::new(self.vitesse.x * dt, self.vitesse.y * dt);
+        if raycast(tilemap, &self.position, &arrivee, &[TileType::Mur]).is_some() {
+            return false;
+        }
+
+        self.position = arrivee;
+        self.reste > 0.0
+    }
+
+    pub fn degats(&self) -> Degats {
+        Degats {
+            montant: self.degats,
+            direction: self.vitesse.unitaire().unwrap_or(Vecteur2D::new(0.0, 0.0)),
+            recul: self.recul
+        }
+    }
+
+    pub fn touche(&self, position: &Vecteur2D<f32>, rayon: f32) -> bool {
+        Vecteur2D::<f32>::from_points(&self.position, position).norme() < rayon + RAYON_PROJECTILE
+    }
+}
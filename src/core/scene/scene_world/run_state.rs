@@ -0,0 +1,54 @@
+use rand::Rng;
+
+use crate::core::scene::scene_world::inventory::Inventaire;
+use crate::core::scene::scene_world::weapon::ArmeEquipee;
+
+// points gagnes en atteignant le vaisseau, multiplies par le numero du monde quitte
+pub const POINTS_PAR_MONDE: u32 = 100;
+
+// etat de la partie, conserve d'un monde a l'autre : le monde est regenere a chaque vaisseau, pas la partie
+#[derive(Clone)]
+pub struct RunState {
+    pub seed_partie: u64,
+    pub compteur_de_monde: u32, // monde en cours, a partir de 1
+    pub temps_total: f32,
+    pub ennemis_evites: u32,
+    pub ennemis_tues: u32,
+    pub monnaie: u32,
+    pub score: u32,
+    pub ameliorations: Vec<String>, // ameliorations choisies entre les mondes, dans l'ordre
+    pub choix_en_attente: Vec<String>, // ameliorations proposees mais pas encore choisies (jeu quitte pendant le choix)
+
+    // etat du joueur en quittant le monde precedent, None en debut de partie (valeurs de player.ini)
+    pub sante: Option<f32>,
+    pub inventaire: Option<Inventaire>,
+    pub armes: Option<(Vec<ArmeEquipee>, usize)> // armes avec leurs munitions et index de l'arme active
+}
+
+impl RunState {
+    pub fn nouvelle_partie() -> Self {
+        Self::with_seed(rand::thread_rng().gen::<u64>())
+    }
+
+    pub fn with_seed(seed_partie: u64) -> Self {
+        Self {
+            seed_partie,
+            compteur_de_monde: 1,
+            temps_total: 0.0,
+            ennemis_evites: 0,
+            ennemis_tues: 0,
+            monnaie: 0,
+            score: 0,
+            ameliorations: vec![],
+            choix_en_attente: vec![],
+            sante: None,
+            inventaire: None,
+            armes: None
+        }
+    }
+
+    pub fn monde_suivant(&mut self) {
+        self.score += POINTS_PAR_MONDE * self.compteur_de_monde;
+        self.compteur_de_monde += 1;
+    }
+}
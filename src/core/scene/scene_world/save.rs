@@ -0,0 +1,414 @@
+use std::fs;
+use std::path::Path;
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::elements::tilemap::tile::TileType;
+use crate::core::scene::scene_world::enemy::Enemy;
+use crate::core::scene::scene_world::inventory::Inventaire;
+use crate::core::scene::scene_world::pickup::Objet;
+use crate::core::scene::scene_world::run_state::RunState;
+use crate::core::scene::scene_world::scene_world_data::SceneWorldData;
+use crate::core::scene::scene_world::spawner::Spawner;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const SAVE_PATH: &str = "save.ini";
+pub const SAVE_VERSION: u32 = 1;
+
+type Migration = fn(&mut IniFile) -> Result<(), String>;
+
+// MIGRATIONS[n] fait passer une sauvegarde de la version n + 1 a la version n + 2
+const MIGRATIONS: &[Migration] = &[];
+
+impl SceneWorldData {
+    pub fn save_exists() -> bool {
+        Path::new(SAVE_PATH).exists()
+    }
+
+    pub fn delete_save() -> Result<(), String> {
+        if Self::save_exists() {
+            fs::remove_file(SAVE_PATH).map_err(|err| format!("impossible de supprimer la sauvegarde : {}", err))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.to_ini().save(SAVE_PATH)
+    }
+
+    pub fn load() -> Result<Self, String> {
+        Self::from_ini(IniFile::load(SAVE_PATH)?)
+    }
+
+    // seuls la graine et les tuiles modifiees sont sauvegardees, pas la tilemap entiere
+    fn to_ini(&self) -> IniFile {
+        let mut fichier = IniFile::default();
+
+        fichier.section_mut("save").set("version", SAVE_VERSION);
+
+        let monde = fichier.section_mut("monde");
+        monde.set("seed", self.tilemap.seed);
+        monde.set("joueur", vecteur_to_string(&self.player.pos));
+        monde.set("vaisseau", vecteur_to_string(&self.vaisseau_a_trouver.position));
+        monde.set("temps", self.spawner.temps_dans_le_monde);
+        monde.set("sante", self.player.sante.points);
+
+        let partie = fichier.section_mut("partie");
+        partie.set("seed", self.run.seed_partie);
+        partie.set("monde", self.run.compteur_de_monde);
+        partie.set("temps", self.run.temps_total);
+        partie.set("evites", self.run.ennemis_evites);
+        partie.set("tues", self.run.ennemis_tues);
+        partie.set("monnaie", self.run.monnaie);
+        partie.set("score", self.run.score);
+        partie.set("ameliorations", self.run.ameliorations.join(", "));
+        partie.set("choix_en_attente", self.run.choix_en_attente.join(", "));
+
+        let ennemis = fichier.section_mut("ennemis");
+        self.enemies.iter().for_each(|enemy| {
+            ennemis.push("ennemi", format!("{}, {}", vecteur_to_string(&enemy.collide_body.position), enemy.archetype.nom))
+        });
+
+        let objets = fichier.section_mut("objets");
+        self.objets.iter().for_each(|objet| {
+            objets.push("objet", format!("{}, {}", vecteur_to_string(&objet.collide_body.position), objet.definition.nom))
+        });
+
+        let inventaire = fichier.section_mut("inventaire");
+        self.player.inventaire.piles.iter().for_each(|pile| {
+            inventaire.push("item", format!("{}, {}", pile.definition.nom, pile.quantite))
+        });
+        self.player.inventaire.equipes.iter().for_each(|definition| {
+            inventaire.push("equipe", definition.nom.clone())
+        });
+
+        let armes = fichier.section_mut("armes");
+        armes.set("active", self.player.arme_active);
+        self.player.armes.iter().for_each(|equipee| {
+            armes.push("arme", format!("{}, {}, {}", equipee.arme.nom, equipee.chargeur, equipee.reserve))
+        });
+
+        let tuiles = fichier.section_mut("tuiles");
+        self.tilemap.modifications.iter().for_each(|((x, y), r#type)| {
+            tuiles.push("tuile", format!("{}, {}, {}", x, y, r#type.code()))
+        });
+
+        fichier
+    }
+
+    fn from_ini(mut fichier: IniFile) -> Result<Self, String> {
+        migrer(&mut fichier)?;
+
+        let monde = section(&fichier, "monde")?;
+        let seed = monde.get("seed")
+            .and_then(|valeur| valeur.parse::<u64>().ok())
+            .ok_or("sauvegarde : graine invalide")?;
+
+        let partie = section(&fichier, "partie")?;
+        let compteur = partie.get("monde")
+            .and_then(|valeur| valeur.parse::<u32>().ok())
+            .ok_or("sauvegarde : compteur de monde invalide")?;
+        // l'etat du joueur est relu plus bas, une fois les items et les armes charges
+        let run = RunState {
+            compteur_de_monde: compteur,
+            temps_total: partie.get_or("temps", 0.0),
+            ennemis_evites: partie.get_or("evites", 0),
+            ennemis_tues: partie.get_or("tues", 0),
+            monnaie: partie.get_or("monnaie", 0),
+            score: partie.get_or("score", 0),
+            ameliorations: partie.get_list("ameliorations"),
+            choix_en_attente: partie.get_list("choix_en_attente"),
+            ..RunState::with_seed(partie.get_or("seed", seed))
+        };
+
+        let mut data = SceneWorldData::with_seed(seed, run);
+
+        let pos_joueur = vecteur_from_section(monde, "joueur")?;
+        data.player.pos = pos_joueur.clone();
+        data.player.collide_body.position = pos_joueur.clone();
+        data.camera = pos_joueur;
+        data.vaisseau_a_trouver.position = vecteur_from_section(monde, "vaisseau")?;
+        data.spawner = Spawner::new(data.spawner.courbe.clone(), compteur, monde.get_or("temps", 0.0));
+
+        data.enemies = section(&fichier, "ennemis")?
+            .get_all("ennemi")
+            .into_iter()
+            .map(|valeur| match valeur.rsplit_once(',') {
+                Some((position, nom)) => {
+                    let archetype = data.archetypes.get(nom.trim())
+                        .ok_or(format!("sauvegarde : ennemi inconnu '{}'", nom.trim()))?;
+                    vecteur_from_str(position).map(|pos| Enemy::new(pos, archetype))
+                }
+                None => Err(format!("sauvegarde : ennemi invalide '{}'", valeur))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        data.objets = section(&fichier, "objets")?
+            .get_all("objet")
+            .into_iter()
+            .map(|valeur| match valeur.rsplit_once(',') {
+                Some((position, nom)) => {
+                    let definition = data.butins.get(nom.trim())
+                        .ok_or(format!("sauvegarde : objet inconnu '{}'", nom.trim()))?;
+                    vecteur_from_str(position).map(|pos| Objet::new(pos, definition))
+                }
+                None => Err(format!("sauvegarde : objet invalide '{}'", valeur))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let section_inventaire = section(&fichier, "inventaire")?;
+        let mut inventaire = Inventaire::new(data.player.inventaire.capacite);
+        let item = |nom: &str| data.items.get(nom).cloned().ok_or(format!("sauvegarde : item inconnu '{}'", nom));
+
+        section_inventaire.get_all("item")
+            .into_iter()
+            .try_for_each(|valeur| match valeur.rsplit_once(',') {
+                Some((nom, quantite)) => {
+                    let quantite = quantite.trim().parse::<u32>()
+                        .map_err(|_| format!("sauvegarde : item invalide '{}'", valeur))?;
+                    inventaire.ajouter(&item(nom.trim())?, quantite);
+                    Ok(())
+                }
+                None => Err(format!("sauvegarde : item invalide '{}'", valeur))
+            })?;
+        inventaire.equipes = section_inventaire.get_all("equipe")
+            .into_iter()
+            .map(item)
+            .collect::<Result<Vec<_>, String>>()?;
+
+        data.player.reprendre_inventaire(inventaire);
+        // apres les bonus d'equipement, qui changent le maximum
+        data.player.sante.points = monde.get_or("sante", data.player.sante.max).min(data.player.sante.max);
+
+        let armes = section(&fichier, "armes")?;
+        armes.get_all("arme")
+            .into_iter()
+            .try_for_each(|valeur| {
+                let champs = valeur.split(',').map(|champ| champ.trim()).collect::<Vec<_>>();
+                match champs.as_slice() {
+                    [nom, chargeur, reserve] => {
+                        if let Some(equipee) = data.player.armes.iter_mut().find(|equipee| equipee.arme.nom == *nom) {
+                            equipee.chargeur = chargeur.parse().map_err(|_| format!("sauvegarde : arme invalide '{}'", valeur))?;
+                            equipee.reserve = reserve.parse().map_err(|_| format!("sauvegarde : arme invalide '{}'", valeur))?;
+                        }
+                        Ok(())
+                    }
+                    _ => Err(format!("sauvegarde : arme invalide '{}'", valeur))
+                }
+            })?;
+        data.player.equiper(armes.get_or("active", 0));
+
+        section(&fichier, "tuiles")?
+            .get_all("tuile")
+            .into_iter()
+            .try_for_each(|valeur| {
+                let champs = valeur.split(',').map(|champ| champ.trim()).collect::<Vec<_>>();
+                match champs.as_slice() {
+                    [x, y, code] => {
+                        let index = Vecteur2D::new(
+                            x.parse::<i32>().map_err(|_| format!("sauvegarde : tuile invalide '{}'", valeur))?,
+                            y.parse::<i32>().map_err(|_| format!("sauvegarde : tuile invalide '{}'", valeur))?
+                        );
+                        let r#type = TileType::from_code(code)
+                            .ok_or(format!("sauvegarde : type de tuile inconnu '{}'", code))?;
+                        data.tilemap.set_tile(&index, r#type);
+                        Ok(())
+                    }
+                    _ => Err(format!("sauvegarde : tuile invalide '{}'", valeur))
+                }
+            })?;
+
+        Ok(data)
+    }
+}
+
+fn migrer(fichier: &mut IniFile) -> Result<(), String> {
+    let version = fichier.section("save")
+        .and_then(|section| section.get("version"))
+        .and_then(|valeur| valeur.parse::<u32>().ok())
+        .ok_or("sauvegarde : version absente")?;
+
+    if version == 0 || version > SAVE_VERSION {
+        return Err(format!("sauvegarde : version {} non supportee", version));
+    }
+
+    MIGRATIONS[(version - 1) as usize..]
+        .iter()
+        .try_for_each(|migration| migration(fichier))?;
+
+    fichier.section_mut("save").set("version", SAVE_VERSION);
+    Ok(())
+}
+
+fn section<'a>(fichier: &'a IniFile, nom: &str) -> Result<&'a IniSection, String> {
+    fichier.section(nom).ok_or(format!("sauvegarde : section [{}] absente", nom))
+}
+
+fn vecteur_to_string(vecteur: &Vecteur2D<f32>) -> String {
+    format!("{}, {}", vecteur.x, vecteur.y)
+}
+
+fn vecteur_from_str(valeur: &str) -> Result<Vecteur2D<f32>, String> {
+    let coords = valeur.split(',')
+        .map(|champ| champ.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("sauvegarde : position invalide '{}'", valeur))?;
+
+    match coords.as_slice() {
+        [x, y] => Ok(Vecteur2D::new(*x, *y)),
+        _ => Err(format!("sauvegarde : position invalide '{}'", valeur))
+    }
+}
+
+fn vecteur_from_section(section: &IniSection, cle: &str) -> Result<Vecteur2D<f32>, String> {
+    section.get(cle)
+        .ok_or(format!("sauvegarde : '{}' absent", cle))
+        .and_then(vecteur_from_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // sauvegarde ecrite a la main au format de la version 1
+    const SAUVEGARDE_V1: &str = "\
+[save]
+version = 1
+
+[monde]
+seed = 1234
+joueur = 64, -32
+vaisseau = 640, 480
+temps = 12.5
+sante = 3
+
+[partie]
+seed = 99
+monde = 2
+temps = 80
+evites = 1
+tues = 5
+monnaie = 7
+score = 100
+ameliorations = aimant, surcharge
+choix_en_attente = semelles, camouflage, surcharge
+
+[ennemis]
+ennemi = 96, 96, tank
+
+[objets]
+objet = 10, 20, soin
+
+[inventaire]
+item = trousse, 3
+equipe = bottes
+
+[armes]
+active = 0
+arme = pistolet, 4, 20
+
+[tuiles]
+tuile = 1, -2, mur
+";
+
+    fn partie_en_cours() -> SceneWorldData {
+        let mut run = RunState::with_seed(42);
+        run.compteur_de_monde = 3;
+        run.temps_total = 75.5;
+        run.ennemis_evites = 4;
+        run.ennemis_tues = 7;
+        run.monnaie = 12;
+        run.score = 300;
+        run.ameliorations = vec!["aimant".to_string(), "surcharge".to_string()];
+        run.choix_en_attente = vec!["semelles".to_string(), "propulseur".to_string()];
+
+        let mut data = SceneWorldData::new(run);
+        data.player.pos = Vecteur2D::new(100.5, -40.0);
+        data.player.collide_body.position = data.player.pos.clone();
+        data.vaisseau_a_trouver.position = Vecteur2D::new(-320.0, 256.0);
+
+        let trousse = data.items.get("trousse").cloned().expect("item trousse");
+        let armure = data.items.get("armure").cloned().expect("item armure");
+        data.player.inventaire.ajouter(&trousse, 7);
+        data.player.inventaire.ajouter(&armure, 1);
+        let index_armure = data.player.inventaire.piles.len() - 1;
+        data.player.utiliser_item(index_armure, &mut 0);
+        data.player.sante.points = 2.0;
+        data.player.armes[0].chargeur = 1;
+
+        data.tilemap.set_tile(&Vecteur2D::new(3, -4), TileType::Mur);
+        data
+    }
+
+    #[test]
+    fn sauvegarde_puis_chargement_identiques() {
+        let data = partie_en_cours();
+        let texte = data.to_ini().to_string();
+        let relue = SceneWorldData::from_ini(IniFile::parse(texte.as_str()).expect("sauvegarde lisible"))
+            .expect("sauvegarde chargee");
+
+        assert_eq!(relue.tilemap.seed, data.tilemap.seed);
+        assert_eq!((relue.player.pos.x, relue.player.pos.y), (100.5, -40.0));
+        assert_eq!((relue.vaisseau_a_trouver.position.x, relue.vaisseau_a_trouver.position.y), (-320.0, 256.0));
+        assert_eq!(relue.player.sante.points, 2.0);
+        assert_eq!(relue.player.sante.max, data.player.sante.max);
+
+        assert_eq!(relue.run.seed_partie, 42);
+        assert_eq!(relue.run.compteur_de_monde, 3);
+        assert_eq!(relue.run.temps_total, 75.5);
+        assert_eq!((relue.run.ennemis_evites, relue.run.ennemis_tues), (4, 7));
+        assert_eq!((relue.run.monnaie, relue.run.score), (12, 300));
+        assert_eq!(relue.run.ameliorations, data.run.ameliorations);
+        assert_eq!(relue.run.choix_en_attente, data.run.choix_en_attente);
+
+        assert_eq!(relue.enemies.len(), data.enemies.len());
+        relue.enemies.iter().zip(data.enemies.iter()).for_each(|(a, b)| {
+            assert_eq!(a.archetype.nom, b.archetype.nom);
+            assert_eq!((a.collide_body.position.x, a.collide_body.position.y), (b.collide_body.position.x, b.collide_body.position.y));
+        });
+        assert_eq!(relue.objets.len(), data.objets.len());
+
+        let piles = |data: &SceneWorldData| data.player.inventaire.piles.iter()
+            .map(|pile| (pile.definition.nom.clone(), pile.quantite))
+            .collect::<Vec<_>>();
+        assert_eq!(piles(&relue), vec![("trousse".to_string(), 5), ("trousse".to_string(), 2)]);
+        assert_eq!(relue.player.inventaire.equipes.iter().map(|item| item.nom.as_str()).collect::<Vec<_>>(), vec!["armure"]);
+
+        assert_eq!(relue.player.armes[0].chargeur, 1);
+        assert_eq!(relue.player.armes[0].reserve, data.player.armes[0].reserve);
+        assert!(relue.tilemap.modifications.get(&(3, -4)) == Some(&TileType::Mur));
+    }
+
+    #[test]
+    fn chargement_du_format_v1() {
+        let data = SceneWorldData::from_ini(IniFile::parse(SAUVEGARDE_V1).expect("sauvegarde lisible"))
+            .expect("sauvegarde v1 chargee");
+
+        assert_eq!(data.tilemap.seed, 1234);
+        assert_eq!((data.player.pos.x, data.player.pos.y), (64.0, -32.0));
+        assert_eq!(data.spawner.temps_dans_le_monde, 12.5);
+        assert_eq!(data.player.sante.points, 3.0);
+        assert_eq!((data.run.seed_partie, data.run.compteur_de_monde, data.run.score), (99, 2, 100));
+        assert_eq!(data.run.ameliorations, vec!["aimant".to_string(), "surcharge".to_string()]);
+        assert_eq!(data.run.choix_en_attente, vec!["semelles".to_string(), "camouflage".to_string(), "surcharge".to_string()]);
+        assert_eq!(data.enemies.iter().map(|enemy| enemy.archetype.nom.as_str()).collect::<Vec<_>>(), vec!["tank"]);
+        assert_eq!(data.objets.len(), 1);
+        assert_eq!(data.player.inventaire.piles[0].quantite, 3);
+        assert_eq!(data.player.inventaire.equipes[0].nom, "bottes");
+        assert_eq!((data.player.armes[0].chargeur, data.player.armes[0].reserve), (4, 20));
+        assert!(data.tilemap.modifications.get(&(1, -2)) == Some(&TileType::Mur));
+    }
+
+    #[test]
+    fn version_absente_ou_inconnue_refusee() {
+        let sans_version = SAUVEGARDE_V1.replace("version = 1\n", "");
+        let future = SAUVEGARDE_V1.replace("version = 1", format!("version = {}", SAVE_VERSION + 1).as_str());
+
+        [sans_version, future, SAUVEGARDE_V1.replace("version = 1", "version = 0")]
+            .iter()
+            .for_each(|texte| {
+                let mut fichier = IniFile::parse(texte).expect("sauvegarde lisible");
+                assert!(migrer(&mut fichier).is_err());
+            });
+    }
+}
@@ -1,95 +1,197 @@
-use rand::Rng;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
 use crate::core::elements::tilemap::TileMapHudge;
+use crate::core::elements::tilemap::world_gen::choisir_generateur;
+use crate::core::elements::tilemap::world_gen::reachability::placer_sortie;
+use crate::core::elements::tilemap::world_gen::spawn::{SpawnRules, SPAWNS_PATH};
+use crate::core::elements::tilemap::world_gen::params::{WorldGenParams, WORLDGEN_PATH};
+use crate::core::pathfinding::{Pathfinder, PATHFINDING_PATH};
 use crate::core::physics::collide_body::CollideBody;
+use crate::core::scene::scene_world::archetype::{ArchetypeRegistry, ENEMIES_PATH};
 use crate::core::scene::scene_world::enemy::Enemy;
-use crate::core::scene::scene_world::player::Player;
+use crate::core::scene::scene_world::enemy_ai::{ProfilIa, AI_PATH};
+use crate::core::physics::shapes::ArcDeCercle;
+use crate::core::scene::scene_world::inventory::{DefinitionItem, ITEMS_PATH};
+use crate::core::scene::scene_world::melee::AttaqueMelee;
+use crate::core::scene::scene_world::pickup::{Butins, Objet, LOOT_PATH};
+use crate::core::scene::scene_world::player::{Player, StatsJoueur, PLAYER_PATH};
+use crate::core::scene::scene_world::projectile::Projectile;
+use crate::core::scene::scene_world::run_state::RunState;
+use crate::core::scene::scene_world::spawner::{CourbeDifficulte, Spawner, DIFFICULTY_PATH};
+use crate::core::scene::scene_world::upgrade::{Ameliorations, UPGRADES_PATH};
+use crate::core::scene::scene_world::weapon::{Arme, WEAPONS_PATH};
+use crate::core::sdd::graine::melanger_graine;
 use crate::core::sdd::vecteur2d::Vecteur2D;
 
 pub struct SceneWorldData {
     pub is_init: bool,
     pub player: Player,
     pub camera: Vecteur2D<f32>,
-    pub tilemap: TileMapHudge, // systeme de map basique (si trop grande joue sur les perfs)
+    pub tilemap: TileMapHudge, // monde infini, genere par chunks autour du joueur
     pub pos_curseur: Vecteur2D<f32>,
 
     pub vaisseau_a_trouver: CollideBody, // vaisseau a trouver pour changer de monde
-    pub compteur_de_monde_genere: u32,
 
-    pub enemies: Vec<Enemy>
+    pub enemies: Vec<Enemy>,
+    pub projectiles: Vec<Projectile>,
+    pub objets: Vec<Objet>,
+    pub butins: Butins,
+    pub items: HashMap<String, Rc<DefinitionItem>>,
+    pub coup_en_cours: Option<(ArcDeCercle, f32)>, // arc du dernier coup au corps a corps et duree d'affichage restante
+    pub archetypes: ArchetypeRegistry,
+    pub pathfinder: Pathfinder, // partage par tous les ennemis
+    pub spawner: Spawner,
+    pub run: RunState, // etat de la partie, mis a jour pendant le monde
+
+    // regles d'apparition, gardees pour la vue de debug (F3)
+    pub regles_apparition: SpawnRules,
+    pub point_depart: Vecteur2D<i32>,
+    pub zone_apparition: (Vecteur2D<i32>, Vecteur2D<i32>),
+    pub affiche_zones_apparition: bool,
+    pub touche_debug_pressee: bool,
+    pub touche_inventaire_pressee: bool,
+    pub touche_echap_pressee: bool
 }
 
 impl SceneWorldData {
-    pub fn new(compteur_de_monde_genere: u32) -> Self {
-        let player = Player::new();
-        let pos_player = player.pos.clone();
+    // chaque monde de la partie a sa propre graine, derivee de celle de la partie
+    pub fn new(run: RunState) -> Self {
+        let seed = melanger_graine(run.seed_partie, run.compteur_de_monde as i32, 0);
+        Self::with_seed(seed, run)
+    }
 
-        let nb_biome_w: u32 = 10;
-        let nb_biome_h: u32 = 10;
+    // la tilemap est entierement determinee par la graine (cf sauvegarde),
+    // le joueur reprend l'etat qu'il avait en quittant le monde precedent
+    pub fn with_seed(seed: u64, run: RunState) -> Self {
+        let compteur_de_monde_genere = run.compteur_de_monde;
         let tile_size: u32 = 32;
 
-        let width_per_biome: u32 = 20;
-        let height_per_biome: u32 = 20;
+        let taille_chunk = Vecteur2D::<u32>::new(20, 20);
+        let rayon_chargement: i32 = 2;
+
+        let params = WorldGenParams::load(WORLDGEN_PATH, compteur_de_monde_genere)
+            .expect("erreur lors du chargement des parametres de generation");
+        let generateur = choisir_generateur(params, seed, taille_chunk)
+            .expect("erreur lors du choix du generateur de monde");
+
+        // meme graine, memes positions de depart, d'ennemis et de vaisseau
+        let mut rng = StdRng::seed_from_u64(melanger_graine(seed, 0, 1));
+
+        // le monde est infini : seule la zone ou apparaissent ennemis et vaisseau est bornee,
+        // elle s'agrandit a chaque monde
+        let taille_zone: i32 = 180 + 20 * compteur_de_monde_genere as i32;
+        let coord_min = Vecteur2D::<i32>::new(10, 10);
+        let coord_max = Vecteur2D::<i32>::new(10 + taille_zone, 10 + taille_zone);
+
+        let depart = generateur.player_start();
+        let armes = Arme::load_all(WEAPONS_PATH)
+            .expect("erreur lors du chargement des armes");
+        let stats_joueur = StatsJoueur::load(PLAYER_PATH)
+            .expect("erreur lors du chargement des caracteristiques du joueur");
+        let melee = AttaqueMelee::load(WEAPONS_PATH)
+            .expect("erreur lors du chargement de l'attaque au corps a corps");
+        let mut player = Player::new(Self::centre_tuile(&depart, tile_size), armes, melee, &stats_joueur);
+        player.reprendre_partie(&run);
+
+        // une amelioration retiree de upgrades.ini depuis la sauvegarde est ignoree
+        let ameliorations = Ameliorations::load(UPGRADES_PATH)
+            .expect("erreur lors du chargement des ameliorations");
+        run.ameliorations
+            .iter()
+            .filter_map(|nom| ameliorations.get(nom))
+            .for_each(|amelioration| player.appliquer_amelioration(&amelioration.effet));
+        let pos_player = player.pos.clone();
 
-        let enemies = Self::generate_random_enemies(
-            Vecteur2D::<i32>::new(10, 10),
-            Vecteur2D::<i32>::new((nb_biome_w * width_per_biome - 1u32) as i32 , (nb_biome_h * height_per_biome - 1u32) as i32),
-            tile_size,
-            10 * compteur_de_monde_genere
-        );
+        let regles_apparition = SpawnRules::load(SPAWNS_PATH)
+            .expect("erreur lors du chargement des regles d'apparition");
+
+        let courbe = CourbeDifficulte::load(DIFFICULTY_PATH)
+            .expect("erreur lors du chargement de la courbe de difficulte");
+
+        let profils = ProfilIa::load_all(AI_PATH)
+            .expect("erreur lors du chargement des profils d'ia");
+        let archetypes = ArchetypeRegistry::load(ENEMIES_PATH, &profils)
+            .expect("erreur lors du chargement des ennemis");
+
+        // un point dont le biome n'accueille aucun archetype reste vide
+        let enemies = generateur
+            .spawn_points(&coord_min, &coord_max, &depart, courbe.ennemis_initiaux(compteur_de_monde_genere), &regles_apparition, &mut rng)
+            .iter()
+            .filter_map(|index| {
+                archetypes.tirer_pour_biome(&generateur.tile_at(index.x, index.y), &mut rng)
+                    .map(|archetype| Enemy::new(Self::centre_tuile(index, tile_size), archetype))
+            })
+            .collect::<Vec<_>>();
+
+        let (sortie, chemin) = placer_sortie(&*generateur, &depart, &coord_min, &coord_max, &mut rng);
+        let vaisseau = Self::centre_tuile(&sortie, tile_size);
+
+        // objets poses au sol, places apres la sortie pour ne pas changer son tirage
+        let items = DefinitionItem::load_all(ITEMS_PATH)
+            .expect("erreur lors du chargement des items");
+        let butins = Butins::load(LOOT_PATH, &items)
+            .expect("erreur lors du chargement des objets");
+        let nombre_objets = (butins.objets_par_niveau * compteur_de_monde_genere as f32).round() as u32;
+        let objets = generateur
+            .spawn_points(&coord_min, &coord_max, &depart, nombre_objets, &regles_apparition, &mut rng)
+            .iter()
+            .filter_map(|index| {
+                butins.tirer("monde", &mut rng)
+                    .map(|definition| Objet::new(Self::centre_tuile(index, tile_size), definition))
+            })
+            .collect::<Vec<_>>();
+
+        let mut tilemap = TileMapHudge::new(tile_size, rayon_chargement, seed, generateur);
+        // le couloir creuse est garde comme une modification (sauvegarde, chunks regeneres)
+        chemin.into_iter().for_each(|(index, r#type)| tilemap.set_tile(&index, r#type));
+        tilemap.update_chunks(&pos_player);
 
         Self {
             is_init: false,
             player,
             camera: pos_player.clone(),
-            tilemap: TileMapHudge::new(nb_biome_w, nb_biome_h, tile_size, width_per_biome, height_per_biome),
+            tilemap,
             pos_curseur: pos_player + Vecteur2D::new(32.0, 0.0),
-            vaisseau_a_trouver: CollideBody::basic(
-                Self::random_vaisseau(
-                    &Vecteur2D::<i32>::new(10, 10),
-                    &Vecteur2D::<i32>::new((nb_biome_w * width_per_biome - 1u32) as i32 , (nb_biome_h * height_per_biome - 1u32) as i32),
-                    tile_size,
-                ),
-                16.0
-            ),
-            compteur_de_monde_genere,
-            enemies
+            vaisseau_a_trouver: CollideBody::basic(vaisseau, 16.0),
+            enemies,
+            projectiles: vec![],
+            objets,
+            butins,
+            items,
+            coup_en_cours: None,
+            archetypes,
+            pathfinder: Pathfinder::load(PATHFINDING_PATH)
+                .expect("erreur lors du chargement des parametres de recherche de chemin"),
+            spawner: Spawner::new(courbe, compteur_de_monde_genere, 0.0),
+            run,
+            regles_apparition,
+            point_depart: depart,
+            zone_apparition: (coord_min, coord_max),
+            affiche_zones_apparition: false,
+            touche_debug_pressee: false,
+            touche_inventaire_pressee: false,
+            touche_echap_pressee: false
         }
     }
 
-    fn generate_random_enemies(coord_min: Vecteur2D<i32>, coord_max: Vecteur2D<i32>, tile_size: u32, number: u32) -> Vec<Enemy> {
-        (0..number)
-            .map(|_| Self::generate_random_enemy(&coord_min, &coord_max, tile_size))
-            .collect::<Vec<_>>()
-    }
-
-    fn generate_random_enemy(coord_min: &Vecteur2D<i32>, coord_max: &Vecteur2D<i32>, tile_size: u32) -> Enemy {
-        let mut rnd = rand::thread_rng();
-
-        let pos = Vecteur2D::<f32>::new(
-            rnd.gen_range(coord_min.x..coord_max.x) as f32 * tile_size as f32,
-            rnd.gen_range(coord_min.y..coord_max.y) as f32 * tile_size as f32,
-        );
-
-        Enemy::new(pos)
+    // etat de la partie a emporter dans le monde suivant (ou a la fin de la partie)
+    pub fn etat_de_la_partie(&self) -> RunState {
+        RunState {
+            sante: Some(self.player.sante.points),
+            inventaire: Some(self.player.inventaire.clone()),
+            armes: Some((self.player.armes.clone(), self.player.arme_active)),
+            ..self.run.clone()
+        }
     }
 
-    fn random_vaisseau(coord_min: &Vecteur2D<i32>, coord_max: &Vecteur2D<i32>, tile_size: u32) -> Vecteur2D<f32> {
-        let mut rng = rand::thread_rng();
-
-        let coord = match rng.gen_range(0..3) {
-            0 => coord_max.clone(),
-            1 => Vecteur2D::new(coord_min.x, coord_max.y),
-            _ => Vecteur2D::new(coord_max.x, coord_min.y)
-        };
-
+    fn centre_tuile(index: &Vecteur2D<i32>, tile_size: u32) -> Vecteur2D<f32> {
         Vecteur2D::new(
-            (coord.x * tile_size as i32) as f32,
-            (coord.y * tile_size as i32) as f32,
+            (index.x * tile_size as i32) as f32 + tile_size as f32 / 2.0,
+            (index.y * tile_size as i32) as f32 + tile_size as f32 / 2.0
         )
-
-        // let x = rng.gen_range(min..max);
-        // let y = rng.gen_range(min..max);
-        //
-        // Vecteur2D::new((x * 32) as f32 , (y * 32) as f32)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,130 @@
+use rand::Rng;
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const DIFFICULTY_PATH: &str = "assets/data/difficulty.ini";
+
+// courbe de difficulte (cf difficulty.ini) : difficulte = niveau * par_niveau + minutes * par_minute,
+// chaque grandeur est ensuite base + par_point * difficulte
+#[derive(Clone)]
+pub struct CourbeDifficulte {
+    pub par_niveau: f32,
+    pub par_minute: f32,
+    pub ennemis_par_niveau: f32, // ennemis places a la generation du monde
+    pub intervalle_base: f32,
+    pub intervalle_par_point: f32,
+    pub intervalle_min: f32,
+    pub taille_base: f32,
+    pub taille_par_point: f32,
+    pub vivants_base: f32,
+    pub vivants_par_point: f32,
+    pub marge_ecran: f32 // distance hors de la vue, en pixels, ou apparaissent les vagues
+}
+
+impl CourbeDifficulte {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let fichier = IniFile::load(path)?;
+        let defaut = IniSection::new("");
+        let section = |nom: &str| fichier.section(nom).unwrap_or(&defaut);
+
+        let difficulte = section("difficulte");
+        let vagues = section("vagues");
+
+        Ok(Self {
+            par_niveau: difficulte.get_or("par_niveau", 1.0),
+            par_minute: difficulte.get_or("par_minute", 0.5),
+            ennemis_par_niveau: difficulte.get_or("ennemis_par_niveau", 10.0),
+            intervalle_base: vagues.get_or("intervalle_base", 25.0),
+            intervalle_par_point: vagues.get_or("intervalle_par_point", 1.5),
+            intervalle_min: vagues.get_or("intervalle_min", 6.0),
+            taille_base: vagues.get_or("taille_base", 1.0),
+            taille_par_point: vagues.get_or("taille_par_point", 0.5),
+            vivants_base: vagues.get_or("vivants_base", 10.0),
+            vivants_par_point: vagues.get_or("vivants_par_point", 5.0),
+            marge_ecran: vagues.get_or("marge_ecran", 48.0)
+        })
+    }
+
+    pub fn difficulte(&self, niveau: u32, temps: f32) -> f32 {
+        niveau as f32 * self.par_niveau + temps / 60.0 * self.par_minute
+    }
+
+    pub fn ennemis_initiaux(&self, niveau: u32) -> u32 {
+        (niveau as f32 * self.ennemis_par_niveau).round() as u32
+    }
+
+    pub fn intervalle(&self, difficulte: f32) -> f32 {
+        (self.intervalle_base - self.intervalle_par_point * difficulte).max(self.intervalle_min)
+    }
+
+    pub fn taille_vague(&self, difficulte: f32) -> u32 {
+        (self.taille_base + self.taille_par_point * difficulte).floor().max(0.0) as u32
+    }
+
+    pub fn max_vivants(&self, difficulte: f32) -> u32 {
+        (self.vivants_base + self.vivants_par_point * difficulte).floor().max(0.0) as u32
+    }
+}
+
+// fait arriver des vagues d'ennemis pendant que le joueur reste dans le monde
+pub struct Spawner {
+    pub courbe: CourbeDifficulte,
+    pub temps_dans_le_monde: f32,
+    prochaine_vague: f32
+}
+
+impl Spawner {
+    pub fn new(courbe: CourbeDifficulte, niveau: u32, temps_dans_le_monde: f32) -> Self {
+        let prochaine_vague = courbe.intervalle(courbe.difficulte(niveau, temps_dans_le_monde));
+
+        Self { courbe, temps_dans_le_monde, prochaine_vague }
+    }
+
+    // nombre d'ennemis a faire apparaitre cette frame, dans la limite des ennemis vivants pres de la vue
+    // (la population placee a la generation, dispersee dans le monde, ne bloque pas les vagues)
+    pub fn update(&mut self, dt: f32, niveau: u32, nb_vivants: usize) -> u32 {
+        self.temps_dans_le_monde += dt;
+        self.prochaine_vague -= dt;
+
+        if self.prochaine_vague > 0.0 {
+            return 0;
+        }
+
+        let difficulte = self.difficulte(niveau);
+        self.prochaine_vague = self.courbe.intervalle(difficulte);
+
+        self.courbe.taille_vague(difficulte)
+            .min(self.courbe.max_vivants(difficulte).saturating_sub(nb_vivants as u32))
+    }
+
+    pub fn difficulte(&self, niveau: u32) -> f32 {
+        self.courbe.difficulte(niveau, self.temps_dans_le_monde)
+    }
+
+    // dans la vue ou dans la marge ou arrivent les vagues
+    pub fn est_pres_de_la_vue(&self, position: &Vecteur2D<f32>, camera: &Vecteur2D<f32>, taille_ecran: &Vecteur2D<f32>) -> bool {
+        let marge = self.courbe.marge_ecran;
+
+        position.x >= camera.x - marge && position.x <= camera.x + taille_ecran.x + marge
+            && position.y >= camera.y - marge && position.y <= camera.y + taille_ecran.y + marge
+    }
+
+    // point au hasard sur le bord de la vue (camera = coin haut gauche), decale de la marge vers l'exterieur
+    pub fn point_hors_ecran(&self, camera: &Vecteur2D<f32>, taille_ecran: &Vecteur2D<f32>) -> Vecteur2D<f32> {
+        let mut rng = rand::thread_rng();
+        let marge = self.courbe.marge_ecran;
+
+        let gauche = camera.x - marge;
+        let haut = camera.y - marge;
+        let droite = camera.x + taille_ecran.x + marge;
+        let bas = camera.y + taille_ecran.y + marge;
+
+        match rng.gen_range(0..4) {
+            0 => Vecteur2D::new(rng.gen_range(gauche..droite), haut),
+            1 => Vecteur2D::new(rng.gen_range(gauche..droite), bas),
+            2 => Vecteur2D::new(gauche, rng.gen_range(haut..bas)),
+            _ => Vecteur2D::new(droite, rng.gen_range(haut..bas))
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::rc::Rc;
+
+use rand::Rng;
+
+use crate::core::data::ini::{IniFile, IniSection};
+
+pub const UPGRADES_PATH: &str = "assets/data/upgrades.ini";
+
+// effet d'une amelioration sur le joueur, cumule avec les ameliorations deja choisies
+#[derive(Clone)]
+pub enum EffetAmelioration {
+    Vitesse(f32), // pixels par seconde
+    Ramassage(f32), // pixels ajoutes au rayon de ramassage des objets
+    Degats(f32), // part des degats ajoutee, au tir et au corps a corps (0.1 : +10%)
+    Discretion(f32) // part retiree a la distance de vue des ennemis
+}
+
+// rang de rarete (cf upgrades.ini) : plus le poids est faible, plus ses ameliorations sont rares
+pub struct Rarete {
+    pub nom: String,
+    pub poids: f32,
+    pub couleur: (u8, u8, u8)
+}
+
+pub struct Amelioration {
+    pub nom: String,
+    pub rarete: Rc<Rarete>,
+    pub effet: EffetAmelioration
+}
+
+impl Rarete {
+    fn from_section(path: &str, section: &IniSection) -> Result<Self, String> {
+        let nom = section.nom["rarete.".len()..].to_string();
+
+        let couleur = section.get_list("couleur")
+            .iter()
+            .map(|composante| composante.parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("{} : couleur invalide pour la rarete '{}'", path, nom))?;
+        let couleur = match couleur.as_slice() {
+            [] => (200, 150, 100),
+            [r, g, b] => (*r, *g, *b),
+            _ => return Err(format!("{} : couleur invalide pour la rarete '{}'", path, nom))
+        };
+
+        Ok(Self {
+            poids: section.get_or("poids", 1.0),
+            couleur,
+            nom
+        })
+    }
+}
+
+impl Amelioration {
+    fn from_section(path: &str, section: &IniSection, raretes: &[Rc<Rarete>]) -> Result<Self, String> {
+        let nom = section.nom["amelioration.".len()..].to_string();
+
+        let nom_rarete = section.get("rarete").unwrap_or("");
+        let rarete = raretes.iter()
+            .find(|rarete| rarete.nom == nom_rarete)
+            .cloned()
+            .ok_or(format!("{} : rarete '{}' inconnue pour l'amelioration '{}'", path, nom_rarete, nom))?;
+
+        let valeur = section.get_or("valeur", 0.0);
+        let effet = match section.get("effet").unwrap_or("") {
+            "vitesse" => EffetAmelioration::Vitesse(valeur),
+            "ramassage" => EffetAmelioration::Ramassage(valeur),
+            "degats" => EffetAmelioration::Degats(valeur),
+            "discretion" => EffetAmelioration::Discretion(valeur),
+            autre => return Err(format!("{} : effet inconnu '{}' pour l'amelioration '{}'", path, autre, nom))
+        };
+
+        Ok(Self { nom, rarete, effet })
+    }
+}
+
+// toutes les ameliorations du jeu
+pub struct Ameliorations {
+    ameliorations: Vec<Rc<Amelioration>>
+}
+
+impl Ameliorations {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let fichier = IniFile::load(path)?;
+
+        let raretes = fichier.sections_prefixees("rarete.")
+            .into_iter()
+            .map(|section| Rarete::from_section(path, section).map(Rc::new))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let ameliorations = fichier.sections_prefixees("amelioration.")
+            .into_iter()
+            .map(|section| Amelioration::from_section(path, section, &raretes).map(Rc::new))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self { ameliorations })
+    }
+
+    pub fn get(&self, nom: &str) -> Option<Rc<Amelioration>> {
+        self.ameliorations.iter().find(|amelioration| amelioration.nom == nom).cloned()
+    }
+
+    // `nombre` ameliorations differentes : le rang est tire selon son poids, puis l'amelioration au hasard dans ce rang
+    pub fn tirer_choix<R: Rng + ?Sized>(&self, nombre: usize, rng: &mut R) -> Vec<Rc<Amelioration>> {
+        let mut restantes = self.ameliorations.iter()
+            .filter(|amelioration| amelioration.rarete.poids > 0.0)
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut choix = vec![];
+
+        while choix.len() < nombre && !restantes.is_empty() {
+            let poids = restantes.iter()
+                .map(|amelioration| {
+                    let meme_rang = restantes.iter().filter(|autre| Rc::ptr_eq(&autre.rarete, &amelioration.rarete)).count();
+                    amelioration.rarete.poids / meme_rang as f32
+                })
+                .collect::<Vec<_>>();
+
+            let mut tirage = rng.gen_range(0.0..poids.iter().sum::<f32>());
+            let index = poids.iter()
+                .position(|poids| {
+                    tirage -= poids;
+                    tirage < 0.0
+                })
+                .unwrap_or(restantes.len() - 1);
+
+            choix.push(restantes.remove(index));
+        }
+
+        choix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    const NB_GRAINES: u64 = 200;
+
+    fn rarete(nom: &str, poids: f32) -> Rc<Rarete> {
+        Rc::new(Rarete { nom: nom.to_string(), poids, couleur: (0, 0, 0) })
+    }
+
+    fn catalogue(liste: &[(&str, &Rc<Rarete>)]) -> Ameliorations {
+        Ameliorations {
+            ameliorations: liste.iter()
+                .map(|(nom, rarete)| Rc::new(Amelioration {
+                    nom: nom.to_string(),
+                    rarete: Rc::clone(rarete),
+                    effet: EffetAmelioration::Vitesse(1.0)
+                }))
+                .collect()
+        }
+    }
+
+    fn noms(choix: &[Rc<Amelioration>]) -> Vec<String> {
+        choix.iter().map(|amelioration| amelioration.nom.clone()).collect()
+    }
+
+    #[test]
+    fn choix_distincts_sans_rang_de_poids_nul() {
+        let commune = rarete("commune", 10.0);
+        let rare = rarete("rare", 2.0);
+        let interdite = rarete("interdite", 0.0);
+        let ameliorations = catalogue(&[
+            ("a", &commune), ("b", &commune), ("c", &commune), ("d", &rare), ("e", &rare), ("x", &interdite)
+        ]);
+
+        (0..NB_GRAINES).for_each(|graine| {
+            let mut noms = noms(&ameliorations.tirer_choix(3, &mut StdRng::seed_from_u64(graine)));
+            assert!(!noms.contains(&"x".to_string()), "graine {}", graine);
+
+            noms.sort();
+            noms.dedup();
+            assert_eq!(noms.len(), 3, "graine {}", graine);
+        });
+    }
+
+    #[test]
+    fn moins_d_ameliorations_que_de_choix() {
+        let commune = rarete("commune", 1.0);
+        let interdite = rarete("interdite", 0.0);
+        let ameliorations = catalogue(&[("a", &commune), ("b", &commune), ("x", &interdite)]);
+
+        (0..NB_GRAINES).for_each(|graine| {
+            let mut noms = noms(&ameliorations.tirer_choix(3, &mut StdRng::seed_from_u64(graine)));
+            noms.sort();
+            assert_eq!(noms, vec!["a".to_string(), "b".to_string()], "graine {}", graine);
+        });
+
+        let vides = catalogue(&[("x", &interdite)]);
+        assert!(vides.tirer_choix(3, &mut StdRng::seed_from_u64(0)).is_empty());
+        assert!(catalogue(&[]).tirer_choix(3, &mut StdRng::seed_from_u64(0)).is_empty());
+    }
+
+    // le rang est tire selon son poids, quel que soit le nombre d'ameliorations qu'il contient
+    #[test]
+    fn premier_choix_selon_le_poids_du_rang() {
+        let commune = rarete("commune", 3.0);
+        let epique = rarete("epique", 1.0);
+        let ameliorations = catalogue(&[("a", &commune), ("b", &commune), ("c", &commune), ("z", &epique)]);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut frequences = HashMap::new();
+        (0..4000).for_each(|_| {
+            let premier = ameliorations.tirer_choix(1, &mut rng)[0].rarete.nom.clone();
+            *frequences.entry(premier).or_insert(0) += 1;
+        });
+
+        let part_epique = *frequences.get("epique").unwrap_or(&0) as f32 / 4000.0;
+        assert!((part_epique - 0.25).abs() < 0.03, "epique tire {}", part_epique);
+    }
+}
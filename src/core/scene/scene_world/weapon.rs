@@ -0,0 +1,170 @@
+use rand::Rng;
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::scene::scene_world::projectile::Projectile;
+use crate::core::sdd::vecteur2d::Vecteur2D;
+
+pub const WEAPONS_PATH: &str = "assets/data/weapons.ini";
+
+// arme definie dans weapons.ini (vitesses en pixels par seconde, durees en secondes)
+#[derive(Clone)]
+pub struct Arme {
+    pub nom: String,
+    pub cadence: f32, // tirs par seconde
+    pub dispersion: f32, // angle total du cone de tir, en degres
+    pub vitesse: f32,
+    pub degats: f32,
+    pub recul: f32, // vitesse de recul donnee aux ennemis touches
+    pub duree_vie: f32,
+    pub projectiles_par_tir: u32,
+    pub son: String,
+    pub taille_chargeur: u32,
+    pub munitions_depart: u32, // hors chargeur
+    pub munitions_max: u32, // hors chargeur
+    pub duree_rechargement: f32,
+    pub son_rechargement: String
+}
+
+impl Arme {
+    fn from_section(section: &IniSection) -> Self {
+        Self {
+            nom: section.nom["arme.".len()..].to_string(),
+            cadence: section.get_or("cadence", 4.0),
+            dispersion: section.get_or("dispersion", 0.0),
+            vitesse: section.get_or("vitesse", 600.0),
+            degats: section.get_or("degats", 1.0),
+            recul: section.get_or("recul", 150.0),
+            duree_vie: section.get_or("duree_vie", 1.0),
+            projectiles_par_tir: section.get_or("projectiles_par_tir", 1),
+            son: section.get_or("son", "arme".to_string()),
+            taille_chargeur: section.get_or("taille_chargeur", 12),
+            munitions_depart: section.get_or("munitions_depart", 48),
+            munitions_max: section.get_or("munitions_max", 96),
+            duree_rechargement: section.get_or("duree_rechargement", 1.0),
+            son_rechargement: section.get_or("son_rechargement", "arme".to_string())
+        }
+    }
+
+    // dans l'ordre du fichier
+    pub fn load_all(path: &str) -> Result<Vec<Self>, String> {
+        let armes = IniFile::load(path)?
+            .sections_prefixees("arme.")
+            .into_iter()
+            .map(Self::from_section)
+            .collect::<Vec<_>>();
+
+        if armes.is_empty() {
+            Err(format!("{} : aucune arme definie", path))
+        } else {
+            Ok(armes)
+        }
+    }
+
+    pub fn delai_entre_tirs(&self) -> f32 {
+        1.0 / self.cadence.max(0.01)
+    }
+
+    // projectiles d'un tir vers `cible`, chacun devie au hasard dans le cone de dispersion
+    pub fn tirer(&self, depuis: &Vecteur2D<f32>, cible: &Vecteur2D<f32>) -> Vec<Projectile> {
+        let direction = match Vecteur2D::<f32>::from_points(depuis, cible).unitaire() {
+            Some(direction) => direction,
+            None => return vec![]
+        };
+
+        let mut rng = rand::thread_rng();
+        let angle_base = direction.y.atan2(direction.x);
+        let demi_dispersion = self.dispersion.to_radians() / 2.0;
+
+        (0..self.projectiles_par_tir)
+            .map(|_| {
+                let angle = if demi_dispersion > 0.0 {
+                    angle_base + rng.gen_range(-demi_dispersion..=demi_dispersion)
+                } else {
+                    angle_base
+                };
+
+                Projectile::new(
+                    depuis.clone(),
+                    Vecteur2D::new(angle.cos() * self.vitesse, angle.sin() * self.vitesse),
+                    self.degats,
+                    self.recul,
+                    self.duree_vie,
+                    true
+                )
+            })
+            .collect()
+    }
+}
+
+// arme de l'inventaire du joueur avec ses munitions
+#[derive(Clone)]
+pub struct ArmeEquipee {
+    pub arme: Arme,
+    pub chargeur: u32,
+    pub reserve: u32 // munitions hors chargeur
+}
+
+impl ArmeEquipee {
+    pub fn new(arme: Arme) -> Self {
+        Self {
+            chargeur: arme.taille_chargeur,
+            reserve: arme.munitions_depart,
+            arme
+        }
+    }
+
+    pub fn peut_recharger(&self) -> bool {
+        self.chargeur < self.arme.taille_chargeur && self.reserve > 0
+    }
+
+    // remplit le chargeur depuis la reserve
+    pub fn recharger(&mut self) {
+        let transferees = self.arme.taille_chargeur.saturating_sub(self.chargeur).min(self.reserve);
+        self.chargeur += transferees;
+        self.reserve -= transferees;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arme(taille_chargeur: u32) -> Arme {
+        let mut section = IniSection::new("arme.test");
+        section.set("taille_chargeur", taille_chargeur);
+        Arme::from_section(&section)
+    }
+
+    fn equipee(chargeur: u32, reserve: u32) -> ArmeEquipee {
+        ArmeEquipee { arme: arme(12), chargeur, reserve }
+    }
+
+    #[test]
+    fn recharger_remplit_le_chargeur_depuis_la_reserve() {
+        let mut equipee = equipee(2, 30);
+        assert!(equipee.peut_recharger());
+
+        equipee.recharger();
+        assert_eq!((equipee.chargeur, equipee.reserve), (12, 20));
+        assert!(!equipee.peut_recharger());
+    }
+
+    #[test]
+    fn recharger_vide_une_reserve_insuffisante() {
+        let mut equipee = equipee(2, 5);
+        equipee.recharger();
+        assert_eq!((equipee.chargeur, equipee.reserve), (7, 0));
+        assert!(!equipee.peut_recharger());
+    }
+
+    #[test]
+    fn recharger_un_chargeur_plein_ou_deborde_ne_change_rien() {
+        [(12, 30), (15, 30), (0, 0)].iter().for_each(|(chargeur, reserve)| {
+            let mut equipee = equipee(*chargeur, *reserve);
+            assert!(!equipee.peut_recharger());
+
+            equipee.recharger();
+            assert_eq!((equipee.chargeur, equipee.reserve), (*chargeur, *reserve));
+        });
+    }
+}
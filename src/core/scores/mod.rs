@@ -0,0 +1,133 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::core::data::ini::{IniFile, IniSection};
+use crate::core::scene::scene_world::run_state::RunState;
+
+pub const HIGHSCORES_PATH: &str = "highscores.ini";
+pub const NB_SCORES_MAX: usize = 10;
+
+#[derive(Clone)]
+pub struct HighScore {
+    pub nom: String,
+    pub mondes: u32,
+    pub score: u32,
+    pub temps_total: f32,
+    pub ennemis_evites: u32,
+    pub ennemis_tues: u32,
+    pub seed: u64,
+    pub date: String
+}
+
+pub struct HighScoreTable {
+    pub scores: Vec<HighScore>
+}
+
+impl HighScore {
+    pub fn new(nom: &str, run: &RunState) -> Self {
+        Self {
+            nom: nom.to_string(),
+            mondes: run.compteur_de_monde,
+            score: run.score,
+            temps_total: run.temps_total,
+            ennemis_evites: run.ennemis_evites,
+            ennemis_tues: run.ennemis_tues,
+            seed: run.seed_partie,
+            date: date_du_jour()
+        }
+    }
+
+    fn from_section(section: &IniSection) -> Self {
+        Self {
+            nom: section.get("nom").unwrap_or("???").to_string(),
+            mondes: section.get_or("mondes", 0),
+            score: section.get_or("score", 0),
+            temps_total: section.get_or("temps", 0.0),
+            ennemis_evites: section.get_or("evites", 0),
+            ennemis_tues: section.get_or("tues", 0),
+            seed: section.get_or("seed", 0),
+            date: section.get("date").unwrap_or("").to_string()
+        }
+    }
+
+    fn write_section(&self, section: &mut IniSection) {
+        section.set("nom", self.nom.as_str());
+        section.set("mondes", self.mondes);
+        section.set("score", self.score);
+        section.set("temps", self.temps_total);
+        section.set("evites", self.ennemis_evites);
+        section.set("tues", self.ennemis_tues);
+        section.set("seed", self.seed);
+        section.set("date", self.date.as_str());
+    }
+
+    // "mm:ss"
+    pub fn temps_formate(&self) -> String {
+        let secondes = self.temps_total as u32;
+        format!("{:02}:{:02}", secondes / 60, secondes % 60)
+    }
+}
+
+impl HighScoreTable {
+    // pas encore de fichier : table vide ; un fichier illisible est une erreur, pour ne pas l'ecraser
+    pub fn load() -> Result<Self, String> {
+        if !Path::new(HIGHSCORES_PATH).exists() {
+            return Ok(Self { scores: vec![] });
+        }
+
+        let scores = IniFile::load(HIGHSCORES_PATH)?
+            .sections
+            .iter()
+            .filter(|section| section.nom == "score")
+            .map(HighScore::from_section)
+            .collect::<Vec<_>>();
+
+        Ok(Self { scores })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let fichier = IniFile {
+            sections: self.scores
+                .iter()
+                .map(|score| {
+                    let mut section = IniSection::new("score");
+                    score.write_section(&mut section);
+                    section
+                })
+                .collect::<Vec<_>>()
+        };
+
+        fichier.save(HIGHSCORES_PATH)
+    }
+
+    // meilleur score d'abord, puis plus de mondes explores, puis le plus rapide
+    pub fn ajouter(&mut self, score: HighScore) {
+        self.scores.push(score);
+        self.scores.sort_by(|a, b| {
+            b.score.cmp(&a.score)
+                .then(b.mondes.cmp(&a.mondes))
+                .then(a.temps_total.partial_cmp(&b.temps_total).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        self.scores.truncate(NB_SCORES_MAX);
+    }
+}
+
+// date "aaaa-mm-jj" (utc) a partir du nombre de jours depuis 1970
+fn date_du_jour() -> String {
+    let secondes = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duree| duree.as_secs())
+        .unwrap_or(0);
+
+    let jours = (secondes / 86_400) as i64 + 719_468;
+    let ere = jours.div_euclid(146_097);
+    let jour_ere = jours.rem_euclid(146_097);
+    let annee_ere = (jour_ere - jour_ere / 1_460 + jour_ere / 36_524 - jour_ere / 146_096) / 365;
+    let jour_annee = jour_ere - (365 * annee_ere + annee_ere / 4 - annee_ere / 100);
+    let mois_decale = (5 * jour_annee + 2) / 153;
+    let jour = jour_annee - (153 * mois_decale + 2) / 5 + 1;
+    let mois = if mois_decale < 10 { mois_decale + 3 } else { mois_decale - 9 };
+    let annee = annee_ere + ere * 400 + if mois <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", annee, mois, jour)
+}
@@ -0,0 +1,33 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+// cache de taille bornee : une fois plein, l'entree la plus ancienne est oubliee
+pub struct CacheBorne<K, V> {
+    capacite: usize,
+    valeurs: HashMap<K, V>,
+    ordre: VecDeque<K> // cles de la plus ancienne a la plus recente
+}
+
+impl<K: Eq + Hash + Clone, V> CacheBorne<K, V> {
+    pub fn new(capacite: usize) -> Self {
+        Self {
+            capacite: capacite.max(1),
+            valeurs: HashMap::new(),
+            ordre: VecDeque::new()
+        }
+    }
+
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, cle: K, calcul: F) -> &V {
+        if !self.valeurs.contains_key(&cle) {
+            if self.valeurs.len() >= self.capacite {
+                if let Some(ancienne) = self.ordre.pop_front() {
+                    self.valeurs.remove(&ancienne);
+                }
+            }
+            self.ordre.push_back(cle.clone());
+            self.valeurs.insert(cle.clone(), calcul());
+        }
+
+        &self.valeurs[&cle]
+    }
+}
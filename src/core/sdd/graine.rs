@@ -0,0 +1,8 @@
+// derive une graine a partir d'une autre et de deux coordonnees (splitmix64)
+pub fn melanger_graine(seed: u64, x: i32, y: i32) -> u64 {
+    let mut z = seed ^ (((x as u32 as u64) << 32) | y as u32 as u64);
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
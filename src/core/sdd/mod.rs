@@ -1 +1,3 @@
-pub mod vecteur2d;
\ No newline at end of file
+pub mod vecteur2d;
+pub mod graine;
+pub mod cache;
@@ -0,0 +1,49 @@
+use crate::core::data::ini::IniFile;
+use crate::core::musics::mixer::BusVolumes;
+use crate::core::translations::Langue;
+
+pub const SETTINGS_PATH: &str = "settings.ini";
+
+pub struct Settings {
+    pub langue: Langue,
+    pub audio: BusVolumes
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            langue: Langue::Anglais,
+            audio: BusVolumes::default()
+        }
+    }
+}
+
+impl Settings {
+    // un fichier absent ou invalide donne les reglages par defaut
+    pub fn load() -> Self {
+        let defaut = Self::default();
+
+        match IniFile::load(SETTINGS_PATH) {
+            Ok(fichier) => {
+                let langue = fichier.section("general")
+                    .and_then(|section| section.get("langue"))
+                    .and_then(Langue::from_code)
+                    .unwrap_or(defaut.langue);
+
+                let audio = fichier.section("audio")
+                    .map(BusVolumes::from_section)
+                    .unwrap_or(defaut.audio);
+
+                Self { langue, audio }
+            }
+            Err(_) => defaut
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let mut fichier = IniFile::load(SETTINGS_PATH).unwrap_or_default();
+        fichier.section_mut("general").set("langue", self.langue.code());
+        self.audio.write_section(fichier.section_mut("audio"));
+        fichier.save(SETTINGS_PATH)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::core::translations::Message;
+use crate::core::translations::Message::{Pluriel, Simple};
+
+pub fn table() -> Vec<(&'static str, Message)> {
+    vec![
+        ("menu.wip", Simple("Work in progress")),
+        ("menu.appuyer_echap", Simple("[Press Escape]")),
+        ("menu.appuyer_espace", Simple("[press space]")),
+        ("menu.continuer", Simple("[C] continue")),
+        ("menu.langue", Simple("[L] language : {langue}")),
+        ("menu.son", Simple("[M] sound : {etat}")),
+        ("menu.son_actif", Simple("on")),
+        ("menu.son_coupe", Simple("muted")),
+        ("menu.scores", Simple("[H] high scores")),
+        ("scores.titre", Simple("High scores")),
+        ("scores.vide", Simple("no score yet")),
+        ("scores.ligne", Simple("{rang}. {nom}  {score} pts  lvl {mondes}  {temps}  {tues} kills  {date}")),
+        ("langue.en", Simple("English")),
+        ("langue.fr", Simple("French")),
+
+        ("game_over.titre", Simple("Game Over")),
+        ("game_over.mondes_explores", Pluriel { un: "explored {n} world", autre: "explored {n} worlds" }),
+        ("game_over.score", Simple("score : {n}")),
+        ("game_over.nom", Simple("name : {nom}")),
+        ("game_over.enregistrer", Simple("[Enter] save score")),
+        ("game_over.appuyer_echap", Simple("[press Escape]")),
+
+        ("monde.nb_ennemis", Simple("count enemies = {n}")),
+        ("monde.niveau", Simple("lvl {n}")),
+        ("monde.debug_apparition", Simple("debug: spawn zones (F3)")),
+        ("monde.arme", Simple("[{index}] {arme}  {chargeur} / {reserve}")),
+        ("monde.rechargement", Simple("{arme} : reloading...")),
+        ("monde.monnaie", Simple("credits : {n}")),
+        ("monde.score", Simple("score : {n}")),
+
+        ("amelioration.titre", Simple("World {n} ahead - pick an upgrade")),
+        ("amelioration.choix", Simple("[{touche}] {nom} ({rarete})")),
+        ("amelioration.aide", Simple("[1/2/3] choose")),
+        ("amelioration.semelles", Simple("Light soles")),
+        ("amelioration.propulseur", Simple("Thruster")),
+        ("amelioration.aimant", Simple("Magnet")),
+        ("amelioration.grand_aimant", Simple("Big magnet")),
+        ("amelioration.balles_affutees", Simple("Sharpened bullets")),
+        ("amelioration.surcharge", Simple("Overcharge")),
+        ("amelioration.camouflage", Simple("Camouflage")),
+        ("amelioration.cape_d_ombre", Simple("Shadow cloak")),
+        ("rarete.commune", Simple("common")),
+        ("rarete.rare", Simple("rare")),
+        ("rarete.epique", Simple("epic")),
+        ("effet.vitesse", Simple("move speed +{n}")),
+        ("effet.ramassage", Simple("pickup radius +{n}")),
+        ("effet.degats", Simple("damage +{n}%")),
+        ("effet.discretion", Simple("enemy sight range -{n}%")),
+
+        ("inventaire.titre", Simple("Inventory ({n} / {max})")),
+        ("inventaire.pile", Simple("{item} x{n}")),
+        ("inventaire.equipe", Simple("{item} (equipped)")),
+        ("inventaire.vide", Simple("empty")),
+        ("inventaire.aide", Simple("[Z/S] select  [Enter] use / equip / remove  [I] close")),
+        ("item.trousse", Simple("medkit")),
+        ("item.caisse_munitions", Simple("ammo crate")),
+        ("item.bottes", Simple("boots")),
+        ("item.armure", Simple("armor")),
+        ("item.bouclier", Simple("shield")),
+    ]
+}
@@ -0,0 +1,67 @@
+use crate::core::translations::Message;
+use crate::core::translations::Message::{Pluriel, Simple};
+
+// pas d'accents : la police dpcomic ne contient que les caracteres ascii
+pub fn table() -> Vec<(&'static str, Message)> {
+    vec![
+        ("menu.wip", Simple("En cours de dev")),
+        ("menu.appuyer_echap", Simple("[Appuyez sur Echap]")),
+        ("menu.appuyer_espace", Simple("[appuyez sur espace]")),
+        ("menu.continuer", Simple("[C] continuer")),
+        ("menu.langue", Simple("[L] langue : {langue}")),
+        ("menu.son", Simple("[M] son : {etat}")),
+        ("menu.son_actif", Simple("actif")),
+        ("menu.son_coupe", Simple("coupe")),
+        ("menu.scores", Simple("[H] meilleurs scores")),
+        ("scores.titre", Simple("Meilleurs scores")),
+        ("scores.vide", Simple("aucun score")),
+        ("scores.ligne", Simple("{rang}. {nom}  {score} pts  niv {mondes}  {temps}  {tues} tues  {date}")),
+        ("langue.en", Simple("Anglais")),
+        ("langue.fr", Simple("Francais")),
+
+        ("game_over.titre", Simple("Partie terminee")),
+        ("game_over.mondes_explores", Pluriel { un: "{n} monde explore", autre: "{n} mondes explores" }),
+        ("game_over.score", Simple("score : {n}")),
+        ("game_over.nom", Simple("nom : {nom}")),
+        ("game_over.enregistrer", Simple("[Entree] enregistrer le score")),
+        ("game_over.appuyer_echap", Simple("[appuyez sur Echap]")),
+
+        ("monde.nb_ennemis", Simple("nombre d'ennemis = {n}")),
+        ("monde.niveau", Simple("niv {n}")),
+        ("monde.debug_apparition", Simple("debug : zones d'apparition (F3)")),
+        ("monde.arme", Simple("[{index}] {arme}  {chargeur} / {reserve}")),
+        ("monde.rechargement", Simple("{arme} : rechargement...")),
+        ("monde.monnaie", Simple("credits : {n}")),
+        ("monde.score", Simple("score : {n}")),
+
+        ("amelioration.titre", Simple("Monde {n} en vue - choisissez une amelioration")),
+        ("amelioration.choix", Simple("[{touche}] {nom} ({rarete})")),
+        ("amelioration.aide", Simple("[1/2/3] choisir")),
+        ("amelioration.semelles", Simple("Semelles legeres")),
+        ("amelioration.propulseur", Simple("Propulseur")),
+        ("amelioration.aimant", Simple("Aimant")),
+        ("amelioration.grand_aimant", Simple("Grand aimant")),
+        ("amelioration.balles_affutees", Simple("Balles affutees")),
+        ("amelioration.surcharge", Simple("Surcharge")),
+        ("amelioration.camouflage", Simple("Camouflage")),
+        ("amelioration.cape_d_ombre", Simple("Cape d'ombre")),
+        ("rarete.commune", Simple("commune")),
+        ("rarete.rare", Simple("rare")),
+        ("rarete.epique", Simple("epique")),
+        ("effet.vitesse", Simple("vitesse +{n}")),
+        ("effet.ramassage", Simple("rayon de ramassage +{n}")),
+        ("effet.degats", Simple("degats +{n}%")),
+        ("effet.discretion", Simple("vue des ennemis -{n}%")),
+
+        ("inventaire.titre", Simple("Inventaire ({n} / {max})")),
+        ("inventaire.pile", Simple("{item} x{n}")),
+        ("inventaire.equipe", Simple("{item} (equipe)")),
+        ("inventaire.vide", Simple("vide")),
+        ("inventaire.aide", Simple("[Z/S] choisir  [Entree] utiliser / equiper / retirer  [I] fermer")),
+        ("item.trousse", Simple("trousse de soin")),
+        ("item.caisse_munitions", Simple("caisse de munitions")),
+        ("item.bottes", Simple("bottes")),
+        ("item.armure", Simple("armure")),
+        ("item.bouclier", Simple("bouclier")),
+    ]
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+pub mod en;
+pub mod fr;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Langue {
+    Anglais,
+    Francais
+}
+
+pub enum Message {
+    Simple(&'static str),
+    Pluriel { un: &'static str, autre: &'static str }
+}
+
+impl Langue {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Langue::Anglais => "en",
+            Langue::Francais => "fr"
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "en" => Some(Langue::Anglais),
+            "fr" => Some(Langue::Francais),
+            _ => None
+        }
+    }
+
+    pub fn suivante(&self) -> Self {
+        match self {
+            Langue::Anglais => Langue::Francais,
+            Langue::Francais => Langue::Anglais
+        }
+    }
+
+    // regle de pluriel : en anglais seul 1 est au singulier, en francais 0 et 1 le sont
+    pub fn est_singulier(&self, n: u32) -> bool {
+        match self {
+            Langue::Anglais => n == 1,
+            Langue::Francais => n <= 1
+        }
+    }
+
+    fn table(&self) -> Vec<(&'static str, Message)> {
+        match self {
+            Langue::Anglais => en::table(),
+            Langue::Francais => fr::table()
+        }
+    }
+}
+
+pub struct TranslationService {
+    pub langue: Langue,
+    table: HashMap<&'static str, Message>,
+    table_secours: HashMap<&'static str, Message>
+}
+
+impl TranslationService {
+    pub fn new(langue: Langue) -> Self {
+        Self {
+            langue,
+            table: langue.table().into_iter().collect::<HashMap<_, _>>(),
+            table_secours: Langue::Anglais.table().into_iter().collect::<HashMap<_, _>>()
+        }
+    }
+
+    pub fn set_langue(&mut self, langue: Langue) {
+        self.langue = langue;
+        self.table = langue.table().into_iter().collect::<HashMap<_, _>>();
+    }
+
+    pub fn tr(&self, id: &str) -> String {
+        self.tr_args(id, &[])
+    }
+
+    pub fn tr_args(&self, id: &str, args: &[(&str, String)]) -> String {
+        match self.message(id) {
+            Some(Message::Simple(texte)) => Self::substituer(texte, args),
+            Some(Message::Pluriel { autre, .. }) => Self::substituer(autre, args),
+            None => id.to_string()
+        }
+    }
+
+    // choisit la forme selon n, qui est aussi disponible comme {n} dans le texte
+    pub fn tr_pluriel(&self, id: &str, n: u32, args: &[(&str, String)]) -> String {
+        let mut args_avec_n = vec![("n", n.to_string())];
+        args_avec_n.extend(args.iter().cloned());
+
+        match self.message(id) {
+            Some(Message::Pluriel { un, autre }) => {
+                let texte = if self.langue.est_singulier(n) { un } else { autre };
+                Self::substituer(texte, &args_avec_n)
+            }
+            Some(Message::Simple(texte)) => Self::substituer(texte, &args_avec_n),
+            None => id.to_string()
+        }
+    }
+
+    fn message(&self, id: &str) -> Option<&Message> {
+        self.table.get(id).or(self.table_secours.get(id))
+    }
+
+    fn substituer(texte: &str, args: &[(&str, String)]) -> String {
+        args.iter()
+            .fold(texte.to_string(), |acc, (cle, valeur)| {
+                acc.replace(format!("{{{}}}", cle).as_str(), valeur)
+            })
+    }
+}
@@ -4,7 +4,8 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use sdl2::event::Event;
-use sdl2::mouse::MouseButton;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::{MouseButton, MouseWheelDirection};
 
 use crate::app::factories::font_factory::FontFactory;
 use crate::app::factories::music_factory::MusicFactory;
@@ -16,9 +17,12 @@ use crate::app::musics::MusicServiceImpl;
 use crate::app::times::TimeServiceImpl;
 use crate::core::graphics::CanDrawText;
 use crate::core::graphics::models::color::Color;
-use crate::core::input::CanManageInput;
+use crate::core::input::{CanManageInput, TextInputEvent};
+use crate::core::musics::CanPlayMusic;
 use crate::core::scene::SceneManager;
 use crate::core::sdd::vecteur2d::Vecteur2D;
+use crate::core::settings::Settings;
+use crate::core::translations::TranslationService;
 
 pub mod core;
 pub mod app;
@@ -57,12 +61,15 @@ pub fn main() -> Result<(), String> {
         )
     );
 
+    let settings = Settings::load();
+
     // todo -- services -- instanciez vos services ici 🛸
     let music_service: Rc<RefCell<MusicServiceImpl>> = Rc::new(
         RefCell::new(
-            MusicServiceImpl  {
-                music_factory: Rc::clone(&music_factory)
-            }
+            MusicServiceImpl::new(
+                Rc::clone(&music_factory),
+                settings.audio.clone()
+            )
         )
     );
     let text_service: Rc<RefCell<TextServiceSDL>> = Rc::new(
@@ -84,29 +91,52 @@ pub fn main() -> Result<(), String> {
             sprite_factory: Rc::clone(&sprite_factory),
         }
     ));
+    let translation_service: Rc<RefCell<TranslationService>> = Rc::new(
+        RefCell::new(
+            TranslationService::new(settings.langue)
+        )
+    );
 
     // todo -- initialisation du scene_manager 🤖
     let mut scene_manager = SceneManager::new(
         Rc::clone(&input_service),
         Rc::clone(&text_service),
         Rc::clone(&sprite_service),
-        Rc::clone(&music_service)
+        Rc::clone(&music_service),
+        Rc::clone(&translation_service)
     );
 
     let mut times = TimeServiceImpl::new();
     let mut event_pump = sdl_context.event_pump()?;
+    video_subsystem.text_input().stop();
 
     'running: loop {
         canvas.borrow_mut().clear();
+
+        // la saisie de texte sdl suit la scene (nom du joueur a la fin de partie)
+        let saisie_demandee = input_service.borrow().is_text_input_active();
+        if saisie_demandee != video_subsystem.text_input().is_active() {
+            if saisie_demandee {
+                video_subsystem.text_input().start();
+            } else {
+                video_subsystem.text_input().stop();
+            }
+        }
+        input_service.borrow_mut().clear_text_input();
+        input_service.borrow_mut().clear_mouse_wheel();
         for event in event_pump.poll_iter() {
             match event {
                 Event::Quit { .. } => {
+                    scene_manager.on_quit()?;
                     break 'running;
                 }
                 Event::KeyDown { keycode, .. } => {
                     match keycode {
                         None => {}
                         Some(key) => {
+                            if key == Keycode::Backspace {
+                                input_service.borrow_mut().push_text_input(TextInputEvent::Backspace);
+                            }
                             input_service.borrow_mut().key_down(key.to_string());
                         }
                     }
@@ -119,6 +149,9 @@ pub fn main() -> Result<(), String> {
                         }
                     }
                 }
+                Event::TextInput { text, .. } => {
+                    input_service.borrow_mut().push_text_input(TextInputEvent::Text(text));
+                }
                 Event::MouseMotion { x, y, .. } => {
                     input_service.borrow_mut().update_mouse_position(
                         Vecteur2D::new(x as f32, y as f32)
@@ -138,6 +171,11 @@ pub fn main() -> Result<(), String> {
                         _ => {}
                     }
                 }
+                Event::MouseWheel { y, direction, .. } => {
+                    // certaines souris inversent le sens de defilement
+                    let crans = if direction == MouseWheelDirection::Flipped { -y } else { y };
+                    input_service.borrow_mut().push_mouse_wheel(crans);
+                }
                 _ => {}
             }
         }
@@ -147,6 +185,7 @@ pub fn main() -> Result<(), String> {
 
         // todo -- boucle de gameplay 👾
         scene_manager.update_scene(delta_time);
+        music_service.borrow_mut().update(delta_time)?;
 
         // debug afficha000
         text_service.borrow().create_text(